@@ -0,0 +1,64 @@
+//! Compares `advent_of_code::ints` against splitting and parsing every token, on the real inputs of days that only need integers.
+//!
+//! Run with `cargo bench --bench ints`. Days without an input in `data/inputs` are skipped.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::ints::{ints, uints};
+use advent_of_code::template::read_file;
+use advent_of_code::Day;
+
+const DAYS: [u8; 4] = [4, 5, 6, 9];
+
+fn bench(name: &str, func: impl Fn() -> i64) {
+    let expected = func();
+    let timer = Instant::now();
+    let mut iterations = 0;
+
+    while timer.elapsed() < Duration::from_secs(1) {
+        assert_eq!(black_box(func()), expected);
+        iterations += 1;
+    }
+
+    println!("  {name:<12} {:?}", timer.elapsed() / iterations);
+}
+
+/// The approach the solutions used before: split into tokens and parse every token that is a number.
+///
+/// Tokens are split at every character but digits and `-`, so `1:` is a number too, and every `-` is the sign
+/// of the number right after it, like in [`ints`].
+fn split_and_parse(input: &str) -> i64 {
+    input
+        .split(|c: char| !c.is_ascii_digit() && c != '-')
+        .flat_map(|token| token.split('-').enumerate())
+        .filter_map(|(index, digits)| {
+            let number = digits.parse::<i64>().ok()?;
+            Some(if index == 0 { number } else { -number })
+        })
+        .sum()
+}
+
+fn main() {
+    for day in DAYS.map(|x| Day::new(x).unwrap()) {
+        let path = format!("data/inputs/{day}.txt");
+        if !std::path::Path::new(&path).exists() {
+            println!("Day {day}: skipped, {path} does not exist.");
+            continue;
+        }
+
+        let input = read_file("inputs", day);
+        println!("Day {day} ({} bytes)", input.len());
+        assert_eq!(
+            split_and_parse(&input),
+            ints::<i64, _>(input.as_str()).sum(),
+            "the baseline must extract the same numbers"
+        );
+        bench("split+parse", || split_and_parse(black_box(&input)));
+        bench("ints", || ints::<i64, _>(black_box(&input)).sum());
+        bench("uints", || uints::<i64, _>(black_box(&input)).sum());
+        bench("ints bytes", || {
+            ints::<i64, _>(black_box(input.as_bytes())).sum()
+        });
+    }
+}
@@ -0,0 +1,202 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The answer to a part of a puzzle.
+///
+/// Solutions can return any type that converts into an [`Answer`], e.g. integers or strings.
+/// Answers compare by their normalized form, so `42` equals `"42"` and trailing whitespace in grids is ignored.
+///
+/// ```
+/// # use advent_of_code::Answer;
+/// assert_eq!(Answer::from(42_u32), Answer::from("42"));
+/// assert!(Answer::from("#..\n.#.\n").is_multiline());
+/// ```
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    /// A multi-line answer, e.g. letters drawn on a grid.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Returns `true` if the answer spans multiple lines.
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+
+    /// The canonical form of the answer that is used for comparison and submission.
+    ///
+    /// Text is trimmed, grid lines lose trailing whitespace and leading or trailing blank lines are dropped.
+    #[must_use]
+    pub fn normalized(&self) -> String {
+        match self {
+            Answer::Int(x) => x.to_string(),
+            Answer::Text(s) => s.trim().to_string(),
+            Answer::Grid(lines) => {
+                let lines: Vec<&str> = lines.iter().map(|x| x.trim_end()).collect();
+                let start = lines.iter().position(|x| !x.is_empty()).unwrap_or(0);
+                let end = lines
+                    .iter()
+                    .rposition(|x| !x.is_empty())
+                    .map_or(0, |x| x + 1);
+                lines[start..end.max(start)].join("\n")
+            }
+        }
+    }
+
+    /// Returns `true` if the answer equals `expected` after normalizing both.
+    #[must_use]
+    pub fn matches(&self, expected: &str) -> bool {
+        *self == Answer::from(expected)
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            _ => self.normalized() == other.normalized(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(x) => write!(f, "{x}"),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Grid(lines) => f.write_str(&lines.join("\n")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Int(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Int(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.trim_end().contains('\n') {
+            Answer::Grid(value.lines().map(String::from).collect())
+        } else {
+            Answer::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        value.to_string().into()
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Answer::Grid(value)
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Parses integers into [`Answer::Int`], everything else like [`From<&str>`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse() {
+            Ok(x) => Ok(Answer::Int(x)),
+            Err(_) => Ok(s.into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn compares_normalized() {
+        assert_eq!(Answer::from(42_u64), Answer::from(" 42\n"));
+        assert_eq!(Answer::from(-3_i32), "-3".parse().unwrap());
+        assert_ne!(Answer::from(42_u64), Answer::from("43"));
+        assert!(Answer::from("abc").matches("abc\n"));
+    }
+
+    #[test]
+    fn detects_grids() {
+        let grid = Answer::from("#.#  \n.#.\n\n");
+        assert!(grid.is_multiline());
+        assert_eq!(grid.normalized(), "#.#\n.#.");
+        assert!(grid.matches("\n#.#\n.#.   "));
+        assert!(!Answer::from("single line\n").is_multiline());
+    }
+
+    #[test]
+    fn converts_into_variants() {
+        assert!(matches!(Answer::from(42_u8), Answer::Int(42)));
+        assert!(matches!(Answer::from(-7_i64), Answer::Int(-7)));
+        assert!(matches!(Answer::from(3_usize), Answer::Int(3)));
+        assert!(matches!(Answer::from("abc"), Answer::Text(x) if x == "abc"));
+        assert!(matches!("-12".parse(), Ok(Answer::Int(-12))));
+        assert!(matches!(" x ".parse(), Ok(Answer::Text(x)) if x == " x "));
+
+        let Answer::Grid(lines) = Answer::from("#.\n.#\n".to_string()) else {
+            panic!("expected a grid");
+        };
+        assert_eq!(lines, ["#.", ".#"]);
+        let grid = Answer::from(vec!["##".to_string(), "..".to_string()]);
+        assert!(matches!(&grid, Answer::Grid(x) if x.len() == 2));
+    }
+
+    #[test]
+    fn displays_answers() {
+        assert_eq!(Answer::from(-42_i32).to_string(), "-42");
+        assert_eq!(Answer::from(" text ").to_string(), " text ");
+        assert_eq!(Answer::from("#.\n.#\n").to_string(), "#.\n.#");
+        assert_eq!(
+            Answer::from(vec!["##".to_string(), "..".to_string()]).to_string(),
+            "##\n.."
+        );
+    }
+
+    #[test]
+    fn converts_large_integers() {
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from(u64::MAX).normalized(), u64::MAX.to_string());
+    }
+}
@@ -1,56 +1,44 @@
-use aho_corasick::AhoCorasick;
+use std::sync::OnceLock;
+
+use advent_of_code::patterns::PatternMap;
 
 advent_of_code::solution!(1);
 
-fn to_digit(s: &str) -> u32 {
-    match s {
-        "0" | "zero" => 0,
-        "1" | "one" => 1,
-        "2" | "two" => 2,
-        "3" | "three" => 3,
-        "4" | "four" => 4,
-        "5" | "five" => 5,
-        "6" | "six" => 6,
-        "7" | "seven" => 7,
-        "8" | "eight" => 8,
-        "9" | "nine" => 9,
-        _ => panic!("not a valid digit"),
-    }
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn digits() -> &'static PatternMap<u32> {
+    static DIGITS_MAP: OnceLock<PatternMap<u32>> = OnceLock::new();
+    DIGITS_MAP.get_or_init(|| PatternMap::new(DIGITS.into_iter().zip(0..)).unwrap())
+}
+
+fn digits_and_words() -> &'static PatternMap<u32> {
+    static DIGITS_AND_WORDS_MAP: OnceLock<PatternMap<u32>> = OnceLock::new();
+    DIGITS_AND_WORDS_MAP.get_or_init(|| {
+        let digits = DIGITS.into_iter().zip(0..);
+        let words = WORDS.into_iter().zip(0..);
+        PatternMap::new(digits.chain(words)).unwrap()
+    })
 }
 
-fn sum_calibration_digits(input: &str, digits: &[&str]) -> u32 {
-    let digits = AhoCorasick::new(digits).unwrap();
+fn sum_calibration_digits(input: &str, digits: &PatternMap<u32>) -> Option<u32> {
     input
         .lines()
         .map(|line| {
-            let matches_iter = || {
-                digits
-                    .find_overlapping_iter(line)
-                    .map(|mat| &line[mat.start()..mat.end()])
-            };
-            let first = to_digit(matches_iter().next().unwrap());
-            let last = to_digit(matches_iter().last().unwrap());
-            first * 10 + last
+            let (first, last) = digits.first_and_last(line)?;
+            Some(first.value * 10 + last.value)
         })
-        .sum::<u32>()
+        .sum()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    sum_calibration_digits(input, &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])
-        .to_string()
-        .into()
+pub fn part_one(input: &str) -> Option<u32> {
+    sum_calibration_digits(input, digits())
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    sum_calibration_digits(
-        input,
-        &[
-            "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three",
-            "four", "five", "six", "seven", "eight", "nine",
-        ],
-    )
-    .to_string()
-    .into()
+pub fn part_two(input: &str) -> Option<u32> {
+    sum_calibration_digits(input, digits_and_words())
 }
 
 #[cfg(test)]
@@ -67,7 +55,7 @@ mod tests {
             treb7uchet
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "142");
+        assert_eq!(result, 142);
     }
 
     #[test]
@@ -82,6 +70,6 @@ mod tests {
             7pqrstsixteen
     "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "281");
+        assert_eq!(result, 281);
     }
 }
@@ -1,94 +1,181 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use advent_of_code::model::InputModel;
+use advent_of_code::parse::{parse_all, Input, ParseError, ParseResult};
+
 advent_of_code::solution!(2);
 
-struct CubeSet {
-    red: u32,
-    green: u32,
-    blue: u32,
+/// The bag contents that part one asks about.
+const LIMITS: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+/// Numbers of cubes by color. Colors that are not listed count as zero cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CubeSet(BTreeMap<String, u32>);
+
+/// A color of which a draw showed more cubes than the bag holds.
+#[derive(Debug, PartialEq, Eq)]
+struct Violation {
+    draw: usize,
+    color: String,
+    drawn: u32,
+    limit: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    id: u32,
+    draws: Vec<CubeSet>,
 }
 
-type Bag = Vec<CubeSet>;
-type Game = (u32, Bag);
-
-fn parse_cube_set(s: &str) -> CubeSet {
-    let mut red = 0;
-    let mut green = 0;
-    let mut blue = 0;
-
-    for cube in s.split(", ") {
-        let (qty, color) = cube.split_once(' ').unwrap();
-        let qty = qty.parse().unwrap();
-        match color {
-            "red" => red = qty,
-            "green" => green = qty,
-            "blue" => blue = qty,
-            _ => panic!("invalid color"),
+/// The record of all games. Colors are rendered in alphabetical order.
+#[derive(Debug, PartialEq, Eq)]
+struct Games(Vec<Game>);
+
+impl CubeSet {
+    fn get(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    fn add(&mut self, color: &str, qty: u32) {
+        *self.0.entry(color.to_string()).or_default() += qty;
+    }
+
+    /// Returns the smallest set that contains both sets.
+    fn max(&self, other: &CubeSet) -> CubeSet {
+        let mut result = self.clone();
+        for (color, qty) in &other.0 {
+            let entry = result.0.entry(color.clone()).or_default();
+            *entry = (*entry).max(*qty);
         }
+        result
+    }
+
+    /// Multiplies the numbers of cubes of `colors`.
+    fn power<'a>(&self, colors: impl IntoIterator<Item = &'a str>) -> u32 {
+        colors.into_iter().map(|color| self.get(color)).product()
     }
+}
 
-    CubeSet { red, green, blue }
+impl<'a> FromIterator<(&'a str, u32)> for CubeSet {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(iter: I) -> Self {
+        let mut set = CubeSet::default();
+        iter.into_iter()
+            .for_each(|(color, qty)| set.add(color, qty));
+        set
+    }
 }
 
-fn parse_bag(s: &str) -> Bag {
-    s.split("; ").map(parse_cube_set).collect()
+impl Display for CubeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes: Vec<String> = self
+            .0
+            .iter()
+            .map(|(color, qty)| format!("{qty} {color}"))
+            .collect();
+        f.write_str(&cubes.join(", "))
+    }
 }
 
-fn parse_game(s: &str) -> Game {
-    let (game_id, bag) = s.split_once(": ").unwrap();
-    let game_id = game_id.strip_prefix("Game ").unwrap().parse().unwrap();
-    let bag = parse_bag(bag);
+impl Game {
+    /// Returns the fewest cubes of each color that make every draw possible.
+    fn minimum_bag(&self) -> CubeSet {
+        self.draws
+            .iter()
+            .fold(CubeSet::default(), |bag, draw| bag.max(draw))
+    }
+
+    /// Returns every color of every draw that exceeds the number of cubes in `limits`.
+    fn violations(&self, limits: &CubeSet) -> Vec<Violation> {
+        self.draws
+            .iter()
+            .enumerate()
+            .flat_map(|(draw, set)| {
+                set.0
+                    .iter()
+                    .filter(|(color, qty)| **qty > limits.get(color))
+                    .map(move |(color, qty)| Violation {
+                        draw,
+                        color: color.clone(),
+                        drawn: *qty,
+                        limit: limits.get(color),
+                    })
+            })
+            .collect()
+    }
 
-    (game_id, bag)
+    fn is_possible(&self, limits: &CubeSet) -> bool {
+        self.violations(limits).is_empty()
+    }
 }
 
-fn parse(input: &str) -> Vec<Game> {
-    input.lines().map(parse_game).collect()
+fn parse_cube_set(input: &mut Input) -> ParseResult<CubeSet> {
+    let mut set = CubeSet::default();
+
+    input.sep_by(",", |input| {
+        let qty = input.uint()?;
+        set.add(input.word()?, qty);
+        Ok(())
+    })?;
+
+    Ok(set)
 }
 
-fn is_game_possible(game: &Game) -> Option<u32> {
-    const RED_MAX: u32 = 12;
-    const GREEN_MAX: u32 = 13;
-    const BLUE_MAX: u32 = 14;
+fn parse_game(input: &mut Input) -> ParseResult<Game> {
+    let id = input.labeled("Game", Input::uint)?;
+    input.tag(":")?;
+    let draws = input.sep_by(";", parse_cube_set)?;
 
-    let (game_id, bag) = game;
-    for &CubeSet { red, green, blue } in bag {
-        if (red > RED_MAX) || (green > GREEN_MAX) || (blue > BLUE_MAX) {
-            return None;
-        }
-    }
+    Ok(Game { id, draws })
+}
 
-    Some(*game_id)
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let draws: Vec<String> = self.draws.iter().map(CubeSet::to_string).collect();
+        write!(f, "Game {}: {}", self.id, draws.join("; "))
+    }
 }
 
-fn power_of_game(game: &Game) -> u32 {
-    let mut min_red = 0;
-    let mut min_green = 0;
-    let mut min_blue = 0;
-    let (_, bag) = game;
+impl InputModel for Games {
+    type Error = ParseError;
 
-    for &CubeSet { red, green, blue } in bag {
-        min_red = std::cmp::max(min_red, red);
-        min_green = std::cmp::max(min_green, green);
-        min_blue = std::cmp::max(min_blue, blue);
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_all(input, |input| input.lines(parse_game)).map(Games)
     }
 
-    min_red * min_green * min_blue
+    fn render(&self) -> String {
+        self.0.iter().map(|game| format!("{game}\n")).collect()
+    }
+}
+
+fn parse(input: &str) -> Vec<Game> {
+    Games::parse(input).unwrap().0
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let games = parse(input);
-    let sum: u32 = games.iter().filter_map(is_game_possible).sum();
-    sum.to_string().into()
+pub fn part_one(input: &str) -> Option<u32> {
+    let limits = CubeSet::from_iter(LIMITS);
+    let sum: u32 = parse(input)
+        .iter()
+        .filter(|game| game.is_possible(&limits))
+        .map(|game| game.id)
+        .sum();
+    Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let games = parse(input);
-    let sum: u32 = games.iter().map(power_of_game).sum();
-    sum.to_string().into()
+pub fn part_two(input: &str) -> Option<u32> {
+    let colors = LIMITS.map(|(color, _)| color);
+    let sum: u32 = parse(input)
+        .iter()
+        .map(|game| game.minimum_bag().power(colors))
+        .sum();
+    Some(sum)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generators::{Day02, InputGenerator};
+    use advent_of_code::model::{assert_exact_round_trip, assert_round_trip};
     use indoc::indoc;
 
     #[test]
@@ -101,7 +188,7 @@ mod tests {
             Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "8");
+        assert_eq!(result, 8);
     }
 
     #[test]
@@ -114,6 +201,58 @@ mod tests {
             Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "2286");
+        assert_eq!(result, 2286);
+    }
+
+    #[test]
+    fn test_queries() {
+        const INPUT: &str = indoc! {"
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+            Game 6: 2 purple, 1 red; 3 purple
+        "};
+        let games = parse(INPUT);
+        let limits = CubeSet::from_iter(LIMITS);
+
+        assert_eq!(
+            games[0].minimum_bag(),
+            CubeSet::from_iter([("red", 20), ("green", 13), ("blue", 6)])
+        );
+        assert_eq!(
+            games[0].violations(&limits),
+            [Violation {
+                draw: 0,
+                color: "red".into(),
+                drawn: 20,
+                limit: 12
+            }]
+        );
+
+        // colors without a limit do not fit into the bag.
+        assert!(!games[1].is_possible(&limits));
+        assert_eq!(games[1].violations(&limits).len(), 2);
+        let limits = CubeSet::from_iter([("red", 1), ("purple", 3)]);
+        assert!(games[1].is_possible(&limits));
+        assert_eq!(games[1].minimum_bag().power(["red", "purple"]), 3);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const INPUT: &str = indoc! {"
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        "};
+        let games: Games = assert_round_trip(INPUT);
+        assert_eq!(
+            games.render(),
+            indoc! {"
+                Game 1: 3 blue, 4 red; 6 blue, 2 green, 1 red; 2 green
+                Game 2: 1 blue, 2 green; 4 blue, 3 green, 1 red; 1 blue, 1 green
+            "}
+        );
+        assert_exact_round_trip::<Games>(&games.render());
+
+        for seed in 0..10 {
+            assert_round_trip::<Games>(&Day02.generate(seed, 20));
+        }
     }
 }
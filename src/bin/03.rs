@@ -1,86 +1,60 @@
-use std::collections::HashSet;
+use advent_of_code::grid::{Grid, Spans, OFFSETS_8};
 
 advent_of_code::solution!(3);
 
-type Schematic = Vec<Vec<char>>;
+type Schematic = Grid<char>;
 
 fn parse(input: &str) -> Schematic {
-    input.lines().map(|line| line.chars().collect()).collect()
+    input.parse().unwrap()
 }
 
 fn is_symbol(c: char) -> bool {
     !(c.is_ascii_digit() || c == '.')
 }
 
-fn find_part_number(sch: &Schematic, row: usize, col: usize, cols: usize) -> u32 {
-    let mut start_col = col;
-    while start_col > 0 && sch[row][start_col - 1].is_ascii_digit() {
-        start_col -= 1;
-    }
-    let mut part_number = String::new();
-    let mut i_col = start_col;
-    while i_col < cols && sch[row][i_col].is_ascii_digit() {
-        part_number.push(sch[row][i_col]);
-        i_col += 1;
-    }
-    part_number.parse().unwrap()
-}
-
-fn find_part_numbers(sch: &Schematic, row: usize, col: usize, cols: usize) -> Vec<u32> {
-    #[rustfmt::skip]
-    static NEIGHBORS: [(i32, i32); 8] = [
-        (-1, -1), (-1, 0), (-1, 1),
-        ( 0, -1),          ( 0, 1),
-        ( 1, -1), ( 1, 0), ( 1, 1),
-    ];
-    let mut part_numbers = HashSet::new();
-    for (n_row, n_col) in NEIGHBORS
-        .map(|(d_row, d_col)| ((row as i32 + d_row) as usize, (col as i32 + d_col) as usize))
-    {
-        if sch[n_row][n_col].is_ascii_digit() {
-            let part_number = find_part_number(sch, n_row, n_col, cols);
-            part_numbers.insert(part_number);
-        }
-    }
-    part_numbers.into_iter().collect()
+/// Finds all numbers in the schematic, returns their spans and values indexed by span id.
+fn find_numbers(sch: &Schematic) -> (Spans, Vec<u32>) {
+    let numbers = Spans::runs(sch, char::is_ascii_digit);
+    let values = numbers
+        .iter()
+        .map(|span| span.text(sch).parse().unwrap())
+        .collect();
+    (numbers, values)
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Option<u32> {
     let sch = parse(input);
-    let rows = sch.len();
-    let cols = sch[0].len();
-    let mut sum = 0;
+    let (numbers, values) = find_numbers(&sch);
 
-    for row in 0..rows {
-        for col in 0..cols {
-            if is_symbol(sch[row][col]) {
-                let part_numbers = find_part_numbers(&sch, row, col, cols);
-                sum += part_numbers.iter().sum::<u32>();
-            }
-        }
-    }
+    let sum: u32 = numbers
+        .iter()
+        .filter(|span| {
+            numbers
+                .border_of(span.id, &OFFSETS_8)
+                .iter()
+                .any(|pos| is_symbol(sch[*pos]))
+        })
+        .map(|span| values[span.id])
+        .sum();
 
-    sum.to_string().into()
+    Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str) -> Option<u32> {
     let sch = parse(input);
-    let rows = sch.len();
-    let cols = sch[0].len();
+    let (numbers, values) = find_numbers(&sch);
     let mut sum = 0;
 
-    for row in 0..rows {
-        for col in 0..cols {
-            if sch[row][col] == '*' {
-                let part_numbers = find_part_numbers(&sch, row, col, cols);
-                if part_numbers.len() == 2 {
-                    sum += part_numbers.iter().product::<u32>();
-                }
+    for (pos, &c) in sch.iter() {
+        if c == '*' {
+            let part_numbers = numbers.adjacent_to(pos, &OFFSETS_8);
+            if part_numbers.len() == 2 {
+                sum += part_numbers.iter().map(|id| values[*id]).product::<u32>();
             }
         }
     }
 
-    sum.to_string().into()
+    Some(sum)
 }
 
 #[cfg(test)]
@@ -103,7 +77,7 @@ mod tests {
             .664.598..
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "4361");
+        assert_eq!(result, 4361);
     }
 
     #[test]
@@ -121,6 +95,16 @@ mod tests {
             .664.598..
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "467835");
+        assert_eq!(result, 467835);
+    }
+
+    #[test]
+    fn test_equal_part_numbers() {
+        const INPUT: &str = indoc! {"
+            12*12
+            .....
+        "};
+        assert_eq!(part_one(INPUT).unwrap(), 24);
+        assert_eq!(part_two(INPUT).unwrap(), 144);
     }
 }
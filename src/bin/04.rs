@@ -1,3 +1,6 @@
+use advent_of_code::model::InputModel;
+use advent_of_code::parse::{parse_all, Input, ParseError, ParseResult};
+
 advent_of_code::solution!(4);
 
 #[derive(Debug, Clone, Copy)]
@@ -7,8 +10,19 @@ struct Card {
     matches: u32,
 }
 
-fn card_points(winning: &[&str], picked: &[&str]) -> u32 {
-    let matches = card_matches(winning, picked);
+/// A card as it is written in the input.
+#[derive(Debug, PartialEq, Eq)]
+struct Scratchcard {
+    num: u32,
+    winning: Vec<u32>,
+    picked: Vec<u32>,
+}
+
+/// All cards. Numbers are rendered right-aligned in columns, like in the puzzle input.
+#[derive(Debug, PartialEq, Eq)]
+struct Scratchcards(Vec<Scratchcard>);
+
+fn card_points(matches: u32) -> u32 {
     if matches > 0 {
         u32::pow(2, matches - 1)
     } else {
@@ -16,61 +30,109 @@ fn card_points(winning: &[&str], picked: &[&str]) -> u32 {
     }
 }
 
-fn card_matches(winning: &[&str], picked: &[&str]) -> u32 {
-    picked.iter().filter(|num| winning.contains(num)).count() as u32
+/// Counts the picked numbers that are winning numbers. `winning` has to be sorted.
+fn card_matches(winning: &[u32], picked: &[u32]) -> u32 {
+    picked
+        .iter()
+        .filter(|num| winning.binary_search(num).is_ok())
+        .count() as u32
+}
+
+fn new_card(num: u32, mut winning: Vec<u32>, picked: &[u32]) -> Card {
+    winning.sort_unstable();
+    let matches = card_matches(&winning, picked);
+    Card {
+        num,
+        points: card_points(matches),
+        matches,
+    }
+}
+
+fn parse_card(input: &mut Input) -> ParseResult<Scratchcard> {
+    let num = input.labeled("Card", Input::uint)?;
+    input.tag(":")?;
+    let winning = input.many(Input::uint);
+    input.tag("|")?;
+    let picked = input.many(Input::uint);
+    Ok(Scratchcard {
+        num,
+        winning,
+        picked,
+    })
+}
+
+impl InputModel for Scratchcards {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_all(input, |input| input.lines(parse_card)).map(Scratchcards)
+    }
+
+    fn render(&self) -> String {
+        let width = self
+            .0
+            .iter()
+            .map(|x| x.num)
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+        let numbers = |numbers: &[u32]| {
+            let numbers: Vec<String> = numbers.iter().map(|x| format!("{x:>2}")).collect();
+            numbers.join(" ")
+        };
+        self.0
+            .iter()
+            .map(|card| {
+                format!(
+                    "Card {:>width$}: {} | {}\n",
+                    card.num,
+                    numbers(&card.winning),
+                    numbers(&card.picked)
+                )
+            })
+            .collect()
+    }
 }
 
 fn parse_cards(input: &str) -> Vec<Card> {
-    input
-        .lines()
-        .map(|line| {
-            let (num, numbers) = line.split_once(':').unwrap();
-            let num = num
-                .strip_prefix("Card")
-                .unwrap()
-                .trim_start()
-                .parse()
-                .unwrap();
-            let (winning, picked) = numbers.split_once('|').unwrap();
-            let winning: Vec<_> = winning.split_ascii_whitespace().collect();
-            let picked: Vec<_> = picked.split_ascii_whitespace().collect();
-            let points = card_points(&winning, &picked);
-            let matches = card_matches(&winning, &picked);
-            Card {
-                num,
-                points,
-                matches,
-            }
-        })
+    let cards = Scratchcards::parse(input).unwrap().0;
+    cards
+        .into_iter()
+        .map(|x| new_card(x.num, x.winning, &x.picked))
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    parse_cards(input)
-        .iter()
-        .map(|card| card.points)
-        .sum::<u32>()
-        .to_string()
-        .into()
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(parse_cards(input).iter().map(|card| card.points).sum())
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let mut cards = parse_cards(input);
-    let mut pos = 0;
-    while pos < cards.len() {
-        for i in 0..cards[pos].matches {
-            let index = (cards[pos].num + i) as usize;
-            cards.push(cards[index]);
+/// Counts the cards with all won copies. Every copy of a card wins the same cards, so copies are added up per card
+/// instead of being processed one by one.
+fn count_cards(cards: &[Card]) -> u64 {
+    let mut copies = vec![1; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        // cards are numbered from 1, so card `num` wins copies of the cards at indices `num..num + matches`.
+        let won = card.num as usize..(card.num + card.matches) as usize;
+        for j in won.take_while(|j| *j < cards.len()) {
+            copies[j] += copies[i];
         }
-        pos += 1;
     }
+    copies.iter().sum()
+}
 
-    cards.len().to_string().into()
+pub fn part_two(input: &str) -> Option<u64> {
+    let cards = parse_cards(input);
+    Some(count_cards(&cards))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Variants;
+    use advent_of_code::generators::{Day04, InputGenerator};
+    use advent_of_code::model::assert_exact_round_trip;
+    use advent_of_code::rng::Rng;
     use indoc::indoc;
 
     #[test]
@@ -84,7 +146,7 @@ mod tests {
             Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "13");
+        assert_eq!(result, 13);
     }
 
     #[test]
@@ -98,6 +160,75 @@ mod tests {
             Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "30");
+        assert_eq!(result, 30);
+    }
+
+    /// The previous implementation of part two, which pushes every won copy onto the list of cards.
+    fn count_cards_by_copying(mut cards: Vec<Card>) -> u64 {
+        let mut pos = 0;
+        while pos < cards.len() {
+            for i in 0..cards[pos].matches {
+                let index = (cards[pos].num + i) as usize;
+                cards.push(cards[index]);
+            }
+            pos += 1;
+        }
+        cards.len() as u64
+    }
+
+    /// The previous matching, which searches the unsorted winning numbers.
+    fn card_matches_by_contains(winning: &[u32], picked: &[u32]) -> u32 {
+        picked.iter().filter(|num| winning.contains(num)).count() as u32
+    }
+
+    #[test]
+    fn test_counting_matches_copying() {
+        let mut rng = Rng::new(0xca7d_ca7d_ca7d_ca7d);
+
+        for _ in 0..200 {
+            let len = rng.below(12) as u32 + 1;
+            let cards: Vec<Card> = (1..=len)
+                .map(|num| {
+                    let winning: Vec<u32> =
+                        (0..rng.below(6)).map(|_| rng.below(20) as u32).collect();
+                    // cards never win copies of cards past the end of the table.
+                    let picked: Vec<u32> = (0..rng.below(u64::from(len - num) + 1))
+                        .map(|_| rng.below(20) as u32)
+                        .collect();
+
+                    let card = new_card(num, winning.clone(), &picked);
+                    assert_eq!(card.matches, card_matches_by_contains(&winning, &picked));
+                    card
+                })
+                .collect();
+
+            assert_eq!(count_cards(&cards), count_cards_by_copying(cards));
+        }
+    }
+
+    #[test]
+    fn test_part_two_variants_agree() {
+        let variants = Variants::new()
+            .with("per card", part_two)
+            .with("copying", |input: &str| {
+                Some(count_cards_by_copying(parse_cards(input)))
+            });
+        if let Err(err) = variants.fuzz_generated(&Day04, 12, 0..50) {
+            panic!("{err}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const INPUT: &str = indoc! {"
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        "};
+        let cards: Scratchcards = assert_exact_round_trip(INPUT);
+        assert_eq!(cards.0[0].picked[2], 6);
+
+        for seed in 0..10 {
+            assert_exact_round_trip::<Scratchcards>(&Day04.generate(seed, 150));
+        }
     }
 }
@@ -1,111 +1,112 @@
-use std::{collections::HashMap, ops::Range};
+use advent_of_code::model::InputModel;
+use advent_of_code::parse::{parse_all, Input, ParseError, ParseResult};
+use advent_of_code::ranges::{RangeMap, RangeSet};
 
 advent_of_code::solution!(5);
 
-type AggMap = Vec<(Range<u64>, Range<u64>)>;
+/// Values outside of the mapped source ranges pass through unchanged.
+type AggMap = RangeMap;
 
-fn parse_seeds(input: &str) -> Vec<u64> {
-    input
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split_ascii_whitespace()
-        .map(|num| num.parse().unwrap())
-        .collect()
+/// A map as it is written in the input, with `(destination, source, length)` entries.
+#[derive(Debug, PartialEq, Eq)]
+struct AlmanacMap {
+    name: String,
+    entries: Vec<(u64, u64, u64)>,
 }
 
-fn parse_map(input: &str) -> AggMap {
-    let mut lines = input.lines();
-    // discard first line
-    lines.next().unwrap();
-    lines
-        .map(|line| {
-            let mut parts = line.split_ascii_whitespace();
-            let dst: u64 = parts.next().unwrap().parse().unwrap();
-            let src: u64 = parts.next().unwrap().parse().unwrap();
-            let len: u64 = parts.next().unwrap().parse().unwrap();
-            (src..src + len, dst..dst + len)
-        })
-        .collect()
+#[derive(Debug, PartialEq, Eq)]
+struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<AlmanacMap>,
 }
 
-fn parse(input: &str) -> (Vec<u64>, Vec<AggMap>) {
-    let mut parts = input.split("\n\n");
-    let seeds = parse_seeds(parts.next().unwrap());
-    let mut maps: Vec<_> = parts.map(parse_map).collect();
-
-    for map in maps.iter_mut() {
-        map.sort_by_key(|(a, _)| a.start);
-        let start = map.iter().next().unwrap().0.start;
-        let end = map.iter().last().unwrap().0.end;
-        let mut passthrough = HashMap::new();
-        let mut windows = map.windows(2);
-        while let Some([a, b]) = windows.next() {
-            if a.0.end != b.0.start {
-                passthrough.insert(a.0.end..b.0.start, a.0.end..b.0.start);
-            }
-        }
-        map.extend(passthrough);
-        if start > 0 {
-            map.push((0..start, 0..start));
-        }
-        map.push((end..u64::MAX, end..u64::MAX));
-        map.sort_by_key(|(a, _)| a.start);
+impl AlmanacMap {
+    fn to_agg_map(&self) -> AggMap {
+        self.entries
+            .iter()
+            .map(|&(dst, src, len)| (src..src + len, dst))
+            .collect()
     }
+}
 
-    (seeds, maps)
+fn parse_seeds(input: &mut Input) -> ParseResult<Vec<u64>> {
+    input.labeled("seeds:", |input| Ok(input.many(Input::uint)))
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (seeds, maps) = parse(input);
+fn parse_map(input: &mut Input) -> ParseResult<AlmanacMap> {
+    let header = *input;
+    let name = input
+        .line()
+        .strip_suffix(" map:")
+        .ok_or_else(|| header.error("a header like `seed-to-soil map:`"))?
+        .to_string();
+    let entries = input.lines(|line| Ok((line.uint()?, line.uint()?, line.uint()?)))?;
+    Ok(AlmanacMap { name, entries })
+}
 
-    let mut lowest = u64::MAX;
+impl InputModel for Almanac {
+    type Error = ParseError;
 
-    for seed in seeds.iter() {
-        let mut value = *seed;
-        for map in maps.iter() {
-            for (src, dst) in map.iter() {
-                if src.contains(&value) {
-                    value = value - src.start + dst.start;
-                    break;
-                }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_all(input, |input| {
+            let seeds = parse_seeds(input)?;
+            let maps = input.blocks(parse_map)?;
+            Ok(Almanac { seeds, maps })
+        })
+    }
+
+    fn render(&self) -> String {
+        let seeds: Vec<String> = self.seeds.iter().map(u64::to_string).collect();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for map in &self.maps {
+            input.push_str(&format!("\n{} map:\n", map.name));
+            for (dst, src, len) in &map.entries {
+                input.push_str(&format!("{dst} {src} {len}\n"));
             }
         }
-        if value < lowest {
-            lowest = value;
-        }
+        input
     }
+}
+
+fn parse(input: &str) -> (Vec<u64>, Vec<AggMap>) {
+    let almanac = Almanac::parse(input).unwrap();
+    let maps = almanac.maps.iter().map(AlmanacMap::to_agg_map).collect();
+    (almanac.seeds, maps)
+}
 
-    lowest.to_string().into()
+/// Maps whole ranges of seeds at once, splitting them at the boundaries of each map.
+fn lowest_location(seeds: &RangeSet<u64>, maps: &[AggMap]) -> Option<u64> {
+    maps.iter()
+        .fold(seeds.clone(), |values, map| map.map_set(&values))
+        .min()
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Option<u64> {
     let (seeds, maps) = parse(input);
-    let seeds: Vec<_> = seeds.chunks(2).map(|win| win[0]..win[0] + win[1]).collect();
-
-    let mut lowest = u64::MAX;
-
-    for seed in seeds.iter().cloned().flatten() {
-        let mut value = seed;
-        for map in maps.iter() {
-            for (src, dst) in map.iter() {
-                if src.contains(&value) {
-                    value = value - src.start + dst.start;
-                    break;
-                }
-            }
-        }
-        if value < lowest {
-            lowest = value;
-        }
-    }
 
-    lowest.to_string().into()
+    seeds
+        .iter()
+        .map(|seed| maps.iter().fold(*seed, |value, map| map.get(value)))
+        .min()
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (seeds, maps) = parse(input);
+    let seeds: RangeSet<u64> = seeds.chunks(2).map(|win| win[0]..win[0] + win[1]).collect();
+
+    lowest_location(&seeds, &maps)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Variants;
+    use advent_of_code::generators::{Day05, InputGenerator};
+    use advent_of_code::model::assert_exact_round_trip;
+    use advent_of_code::rng::Rng;
+    use advent_of_code::snapshot::assert_snapshot;
     use indoc::indoc;
+    use std::ops::Range;
 
     #[test]
     fn test_part_one() {
@@ -145,7 +146,7 @@ mod tests {
             56 93 4
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "35");
+        assert_eq!(result, 35);
     }
 
     #[test]
@@ -186,6 +187,179 @@ mod tests {
             56 93 4
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "46");
+        assert_eq!(result, 46);
+    }
+
+    /// The previous implementation of part two, which maps every seed individually
+    /// through the entries as written in the input, without `RangeMap`.
+    fn lowest_location_brute_force(seeds: &[Range<u64>], maps: &[AlmanacMap]) -> Option<u64> {
+        seeds
+            .iter()
+            .cloned()
+            .flatten()
+            .map(|seed| {
+                maps.iter().fold(seed, |value, map| {
+                    map.entries
+                        .iter()
+                        .find(|&&(_, src, len)| (src..src + len).contains(&value))
+                        .map_or(value, |&(dst, src, _)| value - src + dst)
+                })
+            })
+            .min()
+    }
+
+    fn part_two_brute_force(input: &str) -> Option<u64> {
+        let almanac = Almanac::parse(input).unwrap();
+        let seeds: Vec<Range<u64>> = almanac
+            .seeds
+            .chunks(2)
+            .map(|win| win[0]..win[0] + win[1])
+            .collect();
+        lowest_location_brute_force(&seeds, &almanac.maps)
+    }
+
+    #[test]
+    fn test_ranges_match_brute_force() {
+        let mut rng = Rng::new(0x5eed_5eed_5eed_5eed);
+
+        for _ in 0..200 {
+            let almanac_maps: Vec<AlmanacMap> = (0..rng.below(6) + 1)
+                .map(|_| {
+                    let mut entries = vec![];
+                    let mut src = rng.below(10);
+                    for _ in 0..rng.below(5) {
+                        let len = rng.below(20) + 1;
+                        entries.push((rng.below(100), src, len));
+                        src += len + rng.below(5);
+                    }
+                    rng.shuffle(&mut entries);
+                    AlmanacMap {
+                        name: "a-to-b".to_string(),
+                        entries,
+                    }
+                })
+                .collect();
+            let seeds: Vec<Range<u64>> = (0..rng.below(4) + 1)
+                .map(|_| {
+                    let start = rng.below(100);
+                    start..start + rng.below(30) + 1
+                })
+                .collect();
+
+            let maps: Vec<AggMap> = almanac_maps.iter().map(AlmanacMap::to_agg_map).collect();
+            assert_eq!(
+                lowest_location(&seeds.iter().cloned().collect(), &maps),
+                lowest_location_brute_force(&seeds, &almanac_maps)
+            );
+        }
+    }
+
+    #[test]
+    fn test_part_two_variants_agree() {
+        const INPUT: &str = indoc! {"
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+            37 52 2
+            39 0 15
+
+            fertilizer-to-water map:
+            49 53 8
+            0 11 42
+            42 0 7
+            57 7 4
+
+            water-to-light map:
+            88 18 7
+            18 25 70
+
+            light-to-temperature map:
+            45 77 23
+            81 45 19
+            68 64 13
+
+            temperature-to-humidity map:
+            0 69 1
+            1 0 69
+
+            humidity-to-location map:
+            60 56 37
+            56 93 4
+        "};
+
+        let variants = Variants::new()
+            .with("ranges", part_two)
+            .with("brute force", part_two_brute_force);
+        // generated seed ranges are too long for the brute force, so only the example is mutated.
+        if let Err(err) = variants.fuzz_mutated(INPUT, 0..200) {
+            panic!("{err}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const INPUT: &str = indoc! {"
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+        "};
+        let almanac: Almanac = assert_exact_round_trip(INPUT);
+        assert_eq!(almanac.maps[1].name, "soil-to-fertilizer");
+
+        for seed in 0..10 {
+            assert_exact_round_trip::<Almanac>(&Day05.generate(seed, 20));
+        }
+    }
+
+    #[test]
+    fn test_merged_maps_snapshot() {
+        const INPUT: &str = indoc! {"
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+            37 52 2
+            39 0 15
+
+            fertilizer-to-water map:
+            49 53 8
+            0 11 42
+            42 0 7
+            57 7 4
+
+            water-to-light map:
+            88 18 7
+            18 25 70
+
+            light-to-temperature map:
+            45 77 23
+            81 45 19
+            68 64 13
+
+            temperature-to-humidity map:
+            0 69 1
+            1 0 69
+
+            humidity-to-location map:
+            60 56 37
+            56 93 4
+        "};
+        let (_, maps) = parse(INPUT);
+        let merged = maps.iter().fold(AggMap::new(), |acc, map| acc.then(map));
+        assert_snapshot(DAY, "merged-maps", merged.entries());
     }
 }
@@ -1,12 +1,10 @@
+use advent_of_code::ints::uints;
+use advent_of_code::math::count_products_above;
+
 advent_of_code::solution!(6);
 
 fn parse_line_1<'a>(input: &'a str, prefix: &str) -> impl Iterator<Item = u64> + 'a {
-    input
-        .strip_prefix(prefix)
-        .unwrap()
-        .trim()
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
+    uints(input.strip_prefix(prefix).unwrap())
 }
 
 fn parse_1(input: &str) -> Vec<(u64, u64)> {
@@ -34,35 +32,24 @@ fn parse_2(input: &str) -> (u64, u64) {
     (time, distance)
 }
 
-fn winning_distances(time: u64, record_distance: u64) -> u64 {
-    let mut winning_distances = 0;
-    for hold_time in 1..time {
-        let movement_time = time - hold_time;
-        let distance = hold_time * movement_time;
-        if distance > record_distance {
-            winning_distances += 1;
-        }
-    }
-    winning_distances
-}
-
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Option<u64> {
     let mut total_wins_product = 1;
     for (time, record_distance) in parse_1(input) {
-        total_wins_product *= winning_distances(time, record_distance);
+        total_wins_product *= count_products_above(time, record_distance);
     }
-    total_wins_product.to_string().into()
+    Some(total_wins_product)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str) -> Option<u64> {
     let (time, record_distance) = parse_2(input);
-    let winning_distances = winning_distances(time, record_distance);
-    winning_distances.to_string().into()
+    Some(count_products_above(time, record_distance))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Variants;
+    use advent_of_code::generators::Day06;
     use indoc::indoc;
 
     #[test]
@@ -72,7 +59,7 @@ mod tests {
             Distance:  9  40  200
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "288");
+        assert_eq!(result, 288);
     }
 
     #[test]
@@ -82,6 +69,27 @@ mod tests {
             Distance:  9  40  200
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "71503");
+        assert_eq!(result, 71503);
+    }
+
+    /// The previous implementation of part two, which tries every time the button can be held.
+    fn part_two_by_loop(input: &str) -> Option<u64> {
+        let (time, record_distance) = parse_2(input);
+        Some(
+            (0..=time)
+                .filter(|hold| hold * (time - hold) > record_distance)
+                .count() as u64,
+        )
+    }
+
+    #[test]
+    fn test_part_two_variants_agree() {
+        let variants = Variants::new()
+            .with("quadratic", part_two)
+            .with("loop", part_two_by_loop);
+        // two races keep the joined time short enough for the loop.
+        if let Err(err) = variants.fuzz_generated(&Day06, 2, 0..100) {
+            panic!("{err}");
+        }
     }
 }
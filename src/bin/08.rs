@@ -1,103 +1,177 @@
-use std::collections::HashMap;
+use advent_of_code::cycle::{find_cycle, first_common_hit, Hits};
+use advent_of_code::graph::{Graph, NodeId};
+use advent_of_code::math::lcm;
+use advent_of_code::model::InputModel;
+use advent_of_code::parse::{parse_all, Input, ParseError, ParseResult};
 
 advent_of_code::solution!(8);
 
+#[derive(Debug, PartialEq, Eq)]
 enum Direction {
     Left,
     Right,
 }
 
 type Instructions = Vec<Direction>;
-type Network = HashMap<String, (String, String)>;
+/// Every node has a left and a right edge, in that order.
+type Network = Graph;
 
-fn parse(input: &str) -> (Instructions, Network) {
-    let mut lines = input.lines();
-    let instructions = lines
-        .next()
-        .unwrap()
+/// A line of the network, as written in the input.
+#[derive(Debug, PartialEq, Eq)]
+struct Node {
+    label: String,
+    left: String,
+    right: String,
+}
+
+/// The instructions and the lines of the network, in input order.
+#[derive(Debug, PartialEq, Eq)]
+struct Documents {
+    instructions: Instructions,
+    nodes: Vec<Node>,
+}
+
+fn parse_instructions(input: &mut Input) -> ParseResult<Instructions> {
+    let start = *input;
+    input
+        .word()?
         .chars()
         .map(|c| match c {
-            'R' => Direction::Right,
-            'L' => Direction::Left,
-            _ => panic!("invalid direction"),
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(start.error("only `L` and `R`")),
         })
-        .collect();
-    lines.next().unwrap();
-    let network = lines
-        .map(|line| {
-            let entry = line[0..3].to_string();
-            let left = line[7..10].to_string();
-            let right = line[12..15].to_string();
-            (entry, (left, right))
+        .collect()
+}
+
+fn parse_node(input: &mut Input) -> ParseResult<Node> {
+    let label = input.word()?.to_string();
+    input.tag("=")?;
+    input.tag("(")?;
+    let left = input.word()?.to_string();
+    input.tag(",")?;
+    let right = input.word()?.to_string();
+    input.tag(")")?;
+    Ok(Node { label, left, right })
+}
+
+impl InputModel for Documents {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse_all(input, |input| {
+            let instructions = parse_instructions(input)?;
+            let nodes = input.blocks(|input| input.lines(parse_node))?;
+            Ok(Documents {
+                instructions,
+                nodes: nodes.into_iter().flatten().collect(),
+            })
         })
-        .collect();
-    (instructions, network)
+    }
+
+    fn render(&self) -> String {
+        let instructions: String = self
+            .instructions
+            .iter()
+            .map(|x| match x {
+                Direction::Left => 'L',
+                Direction::Right => 'R',
+            })
+            .collect();
+        let nodes: String = self
+            .nodes
+            .iter()
+            .map(|x| format!("{} = ({}, {})\n", x.label, x.left, x.right))
+            .collect();
+        format!("{instructions}\n\n{nodes}")
+    }
 }
 
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while b != 0 {
-        (a, b) = (b, a % b)
+impl Documents {
+    fn network(&self) -> Network {
+        let mut network = Graph::directed();
+        for node in &self.nodes {
+            network.add_edge(&node.label, &node.left, 1);
+            network.add_edge(&node.label, &node.right, 1);
+        }
+        network
     }
-    a
 }
 
-fn lcm(a: u64, b: u64) -> u64 {
-    if a == 0 || b == 0 {
-        0
-    } else {
-        (a * b) / gcd(a, b)
+fn parse(input: &str) -> (Instructions, Network) {
+    let documents = Documents::parse(input).unwrap();
+    let network = documents.network();
+    (documents.instructions, network)
+}
+
+fn step(network: &Network, node: NodeId, direction: &Direction) -> NodeId {
+    let edges = network.edges(node);
+    match direction {
+        Direction::Left => edges[0].to,
+        Direction::Right => edges[1].to,
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Option<u64> {
     let (instructions, network) = parse(input);
+    let goal = network.id("ZZZ")?;
 
-    let mut steps = 0;
-    let mut node = "AAA";
+    let mut steps = 0_u64;
+    let mut node = network.id("AAA")?;
     for instruction in instructions.iter().cycle() {
-        let (left, right) = network.get(node).unwrap();
-        match instruction {
-            Direction::Left => node = left,
-            Direction::Right => node = right,
-        }
+        node = step(&network, node, instruction);
         steps += 1;
-        if node == "ZZZ" {
+        if node == goal {
             break;
         }
     }
 
-    steps.to_string().into()
+    Some(steps)
+}
+
+/// Finds the steps at which a ghost that starts at `start` is on a node ending with `Z`.
+/// States are `(node, instruction index)` pairs, so the walk is guaranteed to cycle.
+fn ghost_hits(start: NodeId, instructions: &Instructions, network: &Network) -> Hits {
+    let cycle = find_cycle((start, 0), |&(node, i)| {
+        (
+            step(network, node, &instructions[i]),
+            (i + 1) % instructions.len(),
+        )
+    });
+    cycle.hits(|(node, _)| network.label(*node).ends_with('Z'))
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str) -> Option<u64> {
     let (instructions, network) = parse(input);
 
-    let nodes: Vec<_> = network.keys().filter(|node| node.ends_with('A')).collect();
-    let mut all_steps = Vec::new();
-    for node in nodes.iter() {
-        let mut steps = 0;
-        let mut position = node.to_owned();
-        for instruction in instructions.iter().cycle() {
-            let (left, right) = network.get(position).unwrap();
-            match instruction {
-                Direction::Left => position = left,
-                Direction::Right => position = right,
-            }
-            steps += 1;
-            if position.ends_with('Z') {
-                all_steps.push(steps);
-                break;
+    let all_hits: Vec<_> = network
+        .nodes()
+        .filter(|node| network.label(*node).ends_with('A'))
+        .map(|node| ghost_hits(node, &instructions, &network))
+        .collect();
+
+    // real inputs are built so that every ghost reaches a `Z` node exactly at multiples of its cycle length.
+    let steps = if all_hits.iter().all(Hits::is_lcm_compatible) {
+        all_hits.iter().try_fold(1_u64, |acc, x| lcm(acc, x.len))?
+    } else {
+        match first_common_hit(&all_hits) {
+            Ok(steps) => steps,
+            Err(e) => {
+                eprintln!("Error: could not find a step where all ghosts are on `Z` nodes: {e}");
+                return None;
             }
         }
-    }
+    };
 
-    let lcm = all_steps.iter().fold(1, |acc, x| lcm(acc, *x));
-    lcm.to_string().into()
+    Some(steps)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::generators::{Day08, InputGenerator};
+    use advent_of_code::model::assert_exact_round_trip;
+    use advent_of_code::snapshot::assert_snapshot;
     use indoc::indoc;
 
     #[test]
@@ -114,7 +188,7 @@ mod tests {
             ZZZ = (ZZZ, ZZZ)
         "};
         let result = part_one(input).unwrap();
-        assert_eq!(result, "2");
+        assert_eq!(result, 2);
         let input: &str = indoc! {"
             LLR
 
@@ -123,7 +197,7 @@ mod tests {
             ZZZ = (ZZZ, ZZZ)
         "};
         let result = part_one(input).unwrap();
-        assert_eq!(result, "6");
+        assert_eq!(result, 6);
     }
 
     #[test]
@@ -141,6 +215,93 @@ mod tests {
             XXX = (XXX, XXX)
         "};
         let result = part_two(input).unwrap();
-        assert_eq!(result, "6");
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn test_part_two_offset_cycles() {
+        // ghosts reach `Z` nodes at steps 2, 5, 8, ... and 3, 5, 7, ..., so the lcm of the first steps is wrong.
+        let input: &str = indoc! {"
+            L
+
+            11A = (11B, XXX)
+            11B = (11Z, XXX)
+            11Z = (11C, XXX)
+            11C = (11D, XXX)
+            11D = (11Z, XXX)
+            22A = (22B, XXX)
+            22B = (22C, XXX)
+            22C = (22Z, XXX)
+            22Z = (22D, XXX)
+            22D = (22Z, XXX)
+            XXX = (XXX, XXX)
+        "};
+        let result = part_two(input).unwrap();
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_part_two_no_common_step() {
+        let input: &str = indoc! {"
+            L
+
+            11A = (11Z, XXX)
+            11Z = (11B, XXX)
+            11B = (11Z, XXX)
+            22A = (22B, XXX)
+            22B = (22Z, XXX)
+            22Z = (22B, XXX)
+            XXX = (XXX, XXX)
+        "};
+        assert_eq!(part_two(input), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const INPUT: &str = indoc! {"
+            RL
+
+            AAA = (BBB, CCC)
+            BBB = (DDD, EEE)
+            CCC = (ZZZ, GGG)
+            DDD = (DDD, DDD)
+            EEE = (EEE, EEE)
+            GGG = (GGG, GGG)
+            ZZZ = (ZZZ, ZZZ)
+        "};
+        let documents: Documents = assert_exact_round_trip(INPUT);
+        assert_eq!(documents.network().id("ZZZ"), Some(5));
+
+        for seed in 0..10 {
+            assert_exact_round_trip::<Documents>(&Day08.generate(seed, 200));
+        }
+    }
+
+    #[test]
+    fn test_ghost_hits_snapshot() {
+        let input: &str = indoc! {"
+            LR
+
+            11A = (11B, XXX)
+            11B = (XXX, 11Z)
+            11Z = (11B, XXX)
+            22A = (22B, XXX)
+            22B = (22C, 22C)
+            22C = (22Z, 22Z)
+            22Z = (22B, 22B)
+            XXX = (XXX, XXX)
+        "};
+        let (instructions, network) = parse(input);
+        let all_hits: Vec<_> = network
+            .nodes()
+            .filter(|node| network.label(*node).ends_with('A'))
+            .map(|node| {
+                (
+                    network.label(node),
+                    ghost_hits(node, &instructions, &network),
+                )
+            })
+            .collect();
+        assert_snapshot(DAY, "ghost-hits", &all_hits);
     }
 }
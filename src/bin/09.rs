@@ -1,47 +1,24 @@
-advent_of_code::solution!(9);
-
-fn parse(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|num| num.parse::<i32>().unwrap())
-                .collect()
-        })
-        .collect()
-}
+use advent_of_code::ints::ints;
+use advent_of_code::poly::{backward, forward};
 
-fn predict_1(seq: &[i32]) -> i32 {
-    let new_seq: Vec<i32> = seq.windows(2).map(|w| w[1] - w[0]).collect();
-    if new_seq.iter().all(|e| *e == 0) {
-        seq[0]
-    } else {
-        seq.last().unwrap() + predict_1(&new_seq)
-    }
-}
+advent_of_code::solution!(9);
 
-fn predict_2(seq: &[i32]) -> i32 {
-    let new_seq: Vec<i32> = seq.windows(2).rev().map(|w| w[0] - w[1]).collect();
-    if new_seq.iter().all(|e| *e == 0) {
-        seq[0]
-    } else {
-        seq.first().unwrap() + predict_1(&new_seq)
-    }
+fn parse(input: &str) -> Vec<Vec<i64>> {
+    input.lines().map(|line| ints(line).collect()).collect()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let sum: i32 = parse(input).iter().map(|h| predict_1(h)).sum();
-    sum.to_string().into()
+pub fn part_one(input: &str) -> Option<i64> {
+    parse(input).iter().map(|h| forward(h, 1).ok()).sum()
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let sum: i32 = parse(input).iter().map(|h| predict_2(h)).sum();
-    sum.to_string().into()
+pub fn part_two(input: &str) -> Option<i64> {
+    parse(input).iter().map(|h| backward(h, 1).ok()).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::property::{check, ints, vecs};
     use indoc::indoc;
 
     #[test]
@@ -52,7 +29,7 @@ mod tests {
             10 13 16 21 30 45
         "};
         let result = part_one(INPUT).unwrap();
-        assert_eq!(result, "114");
+        assert_eq!(result, 114);
     }
 
     #[test]
@@ -63,6 +40,25 @@ mod tests {
             10 13 16 21 30 45
         "};
         let result = part_two(INPUT).unwrap();
-        assert_eq!(result, "2");
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_part_two_extrapolates_reversed_histories() {
+        check(vecs(vecs(ints(-50_i64..50), 1..10), 1..10), |histories| {
+            let render = |reversed: bool| -> String {
+                histories
+                    .iter()
+                    .map(|history| {
+                        let mut values: Vec<String> = history.iter().map(i64::to_string).collect();
+                        if reversed {
+                            values.reverse();
+                        }
+                        values.join(" ") + "\n"
+                    })
+                    .collect()
+            };
+            part_two(&render(false)) == part_one(&render(true))
+        });
     }
 }
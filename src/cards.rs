@@ -0,0 +1,319 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// The card labels of Camel Cards from weakest to strongest.
+pub const CAMEL_CARDS: &str = "23456789TJQKA";
+
+/// The kind of a hand, from weakest to strongest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum HandKind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandKind {
+    /// Returns the kind of a hand from the sizes of its two largest groups of equal cards.
+    #[must_use]
+    pub fn from_groups(largest: usize, second: usize) -> Self {
+        match (largest, second) {
+            (5.., _) => HandKind::FiveOfAKind,
+            (4, _) => HandKind::FourOfAKind,
+            (3, 2..) => HandKind::FullHouse,
+            (3, _) => HandKind::ThreeOfAKind,
+            (2, 2..) => HandKind::TwoPair,
+            (2, _) => HandKind::OnePair,
+            _ => HandKind::HighCard,
+        }
+    }
+}
+
+/// A precomputed sort key of a hand. Keys order like their hands: by kind first, then by the rank of each card in turn.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct HandKey(u64);
+
+impl HandKey {
+    #[must_use]
+    pub fn kind(&self) -> HandKind {
+        const KINDS: [HandKind; 7] = [
+            HandKind::HighCard,
+            HandKind::OnePair,
+            HandKind::TwoPair,
+            HandKind::ThreeOfAKind,
+            HandKind::FullHouse,
+            HandKind::FourOfAKind,
+            HandKind::FiveOfAKind,
+        ];
+        KINDS[(self.0 >> 60) as usize]
+    }
+}
+
+/// How hands are ranked: the order of card labels and which labels are wild.
+///
+/// Wild cards count as whatever card makes the strongest kind, and rank below every other card when breaking ties.
+///
+/// ```
+/// # use advent_of_code::cards::{HandKind, Rules, CAMEL_CARDS};
+/// let rules = Rules::new(CAMEL_CARDS).with_wild('J');
+/// assert_eq!(rules.kind("KTJJT").unwrap(), HandKind::FourOfAKind);
+/// assert!(rules.key("JKKK2").unwrap() < rules.key("QQQQ2").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// The labels from weakest to strongest, with wild labels first.
+    order: Vec<char>,
+    wild: usize,
+}
+
+impl Rules {
+    /// The maximum number of distinct labels.
+    pub const MAX_LABELS: usize = 16;
+    /// The maximum number of cards in a hand.
+    pub const MAX_CARDS: usize = 15;
+
+    /// Creates rules for the labels in `order` from weakest to strongest, without wild cards.
+    ///
+    /// # Panics
+    /// Panics if `order` has duplicate labels or more than [`Rules::MAX_LABELS`] labels.
+    #[must_use]
+    pub fn new(order: &str) -> Self {
+        let order: Vec<char> = order.chars().collect();
+        assert!(
+            order.len() <= Self::MAX_LABELS,
+            "at most {} labels are supported",
+            Self::MAX_LABELS
+        );
+        for (i, label) in order.iter().enumerate() {
+            assert!(!order[..i].contains(label), "duplicate label `{label}`");
+        }
+        Self { order, wild: 0 }
+    }
+
+    /// Makes `label` wild.
+    ///
+    /// # Panics
+    /// Panics if `label` is not part of the rank order.
+    #[must_use]
+    pub fn with_wild(mut self, label: char) -> Self {
+        let i = self
+            .order
+            .iter()
+            .position(|x| *x == label)
+            .unwrap_or_else(|| panic!("unknown label `{label}`"));
+        if i >= self.wild {
+            self.order[self.wild..=i].rotate_right(1);
+            self.wild += 1;
+        }
+        self
+    }
+
+    /// Returns the rank of a label, starting at `0` for the weakest.
+    #[must_use]
+    pub fn rank(&self, label: char) -> Option<usize> {
+        self.order.iter().position(|x| *x == label)
+    }
+
+    #[must_use]
+    pub fn is_wild(&self, label: char) -> bool {
+        self.rank(label).is_some_and(|x| x < self.wild)
+    }
+
+    fn ranks(&self, hand: &str) -> Result<([u8; Self::MAX_CARDS], usize), HandError> {
+        let mut ranks = [0; Self::MAX_CARDS];
+        let mut len = 0;
+        for label in hand.chars() {
+            let rank = self.rank(label).ok_or(HandError::InvalidCard(label))?;
+            *ranks.get_mut(len).ok_or(HandError::TooManyCards)? = rank as u8;
+            len += 1;
+        }
+        Ok((ranks, len))
+    }
+
+    fn kind_of_ranks(&self, ranks: &[u8]) -> HandKind {
+        let mut counts = [0; Self::MAX_LABELS];
+        ranks.iter().for_each(|x| counts[*x as usize] += 1);
+
+        let wild: usize = counts[..self.wild].iter().sum();
+        let (mut largest, mut second) = (0, 0);
+        for &count in &counts[self.wild..] {
+            if count > largest {
+                (largest, second) = (count, largest);
+            } else if count > second {
+                second = count;
+            }
+        }
+
+        // adding every wild card to the largest group always makes the strongest kind.
+        HandKind::from_groups(largest + wild, second)
+    }
+
+    /// Returns the kind of a hand.
+    pub fn kind(&self, hand: &str) -> Result<HandKind, HandError> {
+        let (ranks, len) = self.ranks(hand)?;
+        Ok(self.kind_of_ranks(&ranks[..len]))
+    }
+
+    /// Returns the sort key of a hand. Only keys of hands with the same number of cards are comparable.
+    pub fn key(&self, hand: &str) -> Result<HandKey, HandError> {
+        let (ranks, len) = self.ranks(hand)?;
+        let kind = self.kind_of_ranks(&ranks[..len]) as u64;
+        let cards = ranks
+            .iter()
+            .fold(0, |acc, rank| (acc << 4) | u64::from(*rank));
+        Ok(HandKey((kind << 60) | cards))
+    }
+}
+
+/// An error which can be returned when ranking a hand.
+#[derive(Debug, PartialEq, Eq)]
+pub enum HandError {
+    InvalidCard(char),
+    TooManyCards,
+}
+
+impl Error for HandError {}
+
+impl Display for HandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::InvalidCard(label) => write!(f, "invalid card `{label}`."),
+            HandError::TooManyCards => {
+                write!(f, "hands have at most {} cards.", Rules::MAX_CARDS)
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HandError, HandKind, Rules, CAMEL_CARDS};
+    use std::cmp::Ordering;
+
+    /// Every multiset of 5 labels, as strings in rank order.
+    fn multisets(labels: &[char]) -> Vec<String> {
+        let mut result = vec![];
+        let mut stack = vec![(String::new(), 0)];
+        while let Some((hand, start)) = stack.pop() {
+            if hand.len() == 5 {
+                result.push(hand);
+                continue;
+            }
+            for (i, label) in labels.iter().enumerate().skip(start) {
+                stack.push((format!("{hand}{label}"), i));
+            }
+        }
+        result
+    }
+
+    fn naive_kind(hand: &str) -> HandKind {
+        let mut counts: Vec<usize> = hand
+            .chars()
+            .map(|x| hand.chars().filter(|y| *y == x).count())
+            .collect();
+        counts.sort_unstable();
+        counts.dedup();
+        let largest = counts.pop().unwrap();
+        let distinct = hand
+            .chars()
+            .enumerate()
+            .filter(|(i, x)| !hand[..*i].contains(*x))
+            .count();
+        match (largest, distinct) {
+            (5, _) => HandKind::FiveOfAKind,
+            (4, _) => HandKind::FourOfAKind,
+            (3, 2) => HandKind::FullHouse,
+            (3, _) => HandKind::ThreeOfAKind,
+            (2, 3) => HandKind::TwoPair,
+            (2, _) => HandKind::OnePair,
+            _ => HandKind::HighCard,
+        }
+    }
+
+    /// Tries every replacement of the wild card.
+    fn naive_wild_kind(hand: &str, wild: char) -> HandKind {
+        let Some(i) = hand.find(wild) else {
+            return naive_kind(hand);
+        };
+        CAMEL_CARDS
+            .chars()
+            .filter(|x| *x != wild)
+            .map(|x| naive_wild_kind(&format!("{}{x}{}", &hand[..i], &hand[i + 1..]), wild))
+            .max()
+            .unwrap()
+    }
+
+    fn naive_cmp(a: &str, b: &str, order: &str, kind: impl Fn(&str) -> HandKind) -> Ordering {
+        kind(a).cmp(&kind(b)).then_with(|| {
+            let ranks = |x: &str| x.chars().map(|c| order.find(c)).collect::<Vec<_>>();
+            ranks(a).cmp(&ranks(b))
+        })
+    }
+
+    /// Sorts `hands` by key and checks every neighboring pair against the naive comparison.
+    fn assert_sorted_like_naive(
+        rules: &Rules,
+        mut hands: Vec<String>,
+        order: &str,
+        kind: impl Fn(&str) -> HandKind,
+    ) {
+        hands.sort_by_key(|x| rules.key(x).unwrap());
+        for pair in hands.windows(2) {
+            assert_ne!(
+                naive_cmp(&pair[0], &pair[1], order, &kind),
+                Ordering::Greater,
+                "{} > {}",
+                pair[0],
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn ranks_all_multisets() {
+        let labels: Vec<char> = CAMEL_CARDS.chars().collect();
+        let hands = multisets(&labels);
+        assert_eq!(hands.len(), 6188);
+
+        let rules = Rules::new(CAMEL_CARDS);
+        for hand in &hands {
+            assert_eq!(rules.kind(hand).unwrap(), naive_kind(hand), "{hand}");
+            assert_eq!(rules.key(hand).unwrap().kind(), naive_kind(hand));
+        }
+
+        // rotate the hands so ties are not broken by the first card alone.
+        let rotated = hands.iter().map(|x| format!("{}{}", &x[2..], &x[..2]));
+        assert_sorted_like_naive(&rules, rotated.collect(), CAMEL_CARDS, naive_kind);
+    }
+
+    #[test]
+    fn ranks_all_multisets_with_wild_cards() {
+        let labels: Vec<char> = CAMEL_CARDS.chars().collect();
+        let hands = multisets(&labels);
+        let rules = Rules::new(CAMEL_CARDS).with_wild('J');
+        let kind = |x: &str| naive_wild_kind(x, 'J');
+
+        for hand in &hands {
+            assert_eq!(rules.kind(hand).unwrap(), kind(hand), "{hand}");
+        }
+
+        let rotated = hands.iter().map(|x| format!("{}{}", &x[3..], &x[..3]));
+        assert_sorted_like_naive(&rules, rotated.collect(), "J23456789TQKA", kind);
+    }
+
+    #[test]
+    fn supports_custom_rules() {
+        let rules = Rules::new("AB").with_wild('B').with_wild('B');
+        assert!(rules.is_wild('B') && !rules.is_wild('A'));
+        assert_eq!(rules.rank('B'), Some(0));
+        assert_eq!(rules.kind("ABAB"), Ok(HandKind::FourOfAKind));
+        assert_eq!(rules.kind("BBB"), Ok(HandKind::ThreeOfAKind));
+        assert_eq!(rules.kind("AC"), Err(HandError::InvalidCard('C')));
+        assert_eq!(rules.kind(&"A".repeat(16)), Err(HandError::TooManyCards));
+    }
+}
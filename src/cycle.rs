@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::math::crt;
+
+/// The states of a deterministic sequence up to the point where it starts repeating.
+///
+/// ```
+/// # use advent_of_code::cycle::find_cycle;
+/// // 0, 1, 2, 3, 4, 2, 3, 4, ...
+/// let cycle = find_cycle(0, |x| if *x == 4 { 2 } else { x + 1 });
+/// assert_eq!((cycle.offset, cycle.len), (2, 3));
+/// assert_eq!(*cycle.state_at(1000), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The number of states before the cycle starts.
+    pub offset: usize,
+    /// The number of states in the cycle.
+    pub len: usize,
+    /// The first `offset + len` states, after which the sequence repeats.
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Returns the index into [`Cycle::states`] of the state after `step` steps.
+    #[must_use]
+    pub fn index_of(&self, step: usize) -> usize {
+        if step < self.offset {
+            step
+        } else {
+            self.offset + (step - self.offset) % self.len
+        }
+    }
+
+    /// Returns the state after `step` steps.
+    #[must_use]
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.index_of(step)]
+    }
+
+    /// Returns the steps at which the sequence is in a state that matches `predicate`.
+    pub fn hits(&self, predicate: impl Fn(&S) -> bool) -> Hits {
+        let (prefix, cycle): (Vec<u64>, Vec<u64>) = self
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| predicate(state))
+            .map(|(step, _)| step as u64)
+            .partition(|step| *step < self.offset as u64);
+
+        Hits {
+            offset: self.offset as u64,
+            len: self.len as u64,
+            prefix,
+            cycle,
+        }
+    }
+}
+
+/// Steps the sequence that starts at `start` until a state repeats.
+///
+/// Runs in time and memory proportional to `offset + len`.
+pub fn find_cycle<S: Clone + Eq + Hash>(start: S, mut next: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = start;
+
+    loop {
+        if let Some(&offset) = seen.get(&state) {
+            return Cycle {
+                offset,
+                len: states.len() - offset,
+                states,
+            };
+        }
+
+        seen.insert(state.clone(), states.len());
+        let following = next(&state);
+        states.push(state);
+        state = following;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The steps at which a cyclic sequence is in a matching state, as returned by [`Cycle::hits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hits {
+    pub offset: u64,
+    pub len: u64,
+    /// Matching steps before the cycle starts, in ascending order.
+    pub prefix: Vec<u64>,
+    /// Matching steps in the first pass of the cycle, in ascending order. These repeat every `len` steps.
+    pub cycle: Vec<u64>,
+}
+
+impl Hits {
+    /// Returns `true` if the sequence is in a matching state after `step` steps.
+    #[must_use]
+    pub fn contains(&self, step: u64) -> bool {
+        if step < self.offset {
+            self.prefix.binary_search(&step).is_ok()
+        } else {
+            let step = self.offset + (step - self.offset) % self.len;
+            self.cycle.binary_search(&step).is_ok()
+        }
+    }
+
+    /// Returns `true` if the sequence matches exactly at positive multiples of its cycle length,
+    /// i.e. the first common step of several such sequences is the least common multiple of their lengths.
+    #[must_use]
+    pub fn is_lcm_compatible(&self) -> bool {
+        self.prefix.is_empty() && self.cycle == [self.len]
+    }
+}
+
+/// Returns the first step at which every sequence is in a matching state.
+///
+/// Steps after all prefixes are found by solving the cycle residues with the Chinese Remainder Theorem,
+/// once for every combination of matching steps within the cycles.
+pub fn first_common_hit(hits: &[Hits]) -> Result<u64, CommonHitError> {
+    let Some(longest) = hits.iter().max_by_key(|x| x.offset) else {
+        return Err(CommonHitError::NoSequences);
+    };
+
+    // a common step before all cycles started has to be in the longest prefix.
+    if let Some(step) = longest
+        .prefix
+        .iter()
+        .find(|step| hits.iter().all(|x| x.contains(**step)))
+    {
+        return Ok(*step);
+    }
+
+    let mut combinations: Vec<Vec<(i128, i128)>> = vec![vec![]];
+    for x in hits {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                x.cycle.iter().map(move |step| {
+                    let mut combination = combination.clone();
+                    combination.push((i128::from(*step), i128::from(x.len)));
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    let min_step = i128::from(longest.offset);
+    let mut overflowed = false;
+
+    let first = combinations
+        .into_iter()
+        .filter_map(|combination| {
+            let Some((step, modulus)) = crt(combination.iter().copied()) else {
+                // distinguish overflows from systems without a solution.
+                overflowed |= combination
+                    .iter()
+                    .try_fold(1_i128, |acc, (_, m)| crate::math::lcm(acc, *m))
+                    .is_none();
+                return None;
+            };
+            // lift the solution to the first step where all sequences are in their cycle.
+            let step = if step < min_step {
+                step + (min_step - step + modulus - 1) / modulus * modulus
+            } else {
+                step
+            };
+            match u64::try_from(step) {
+                Ok(step) => Some(step),
+                Err(_) => {
+                    overflowed = true;
+                    None
+                }
+            }
+        })
+        .min();
+
+    match first {
+        Some(step) => Ok(step),
+        None if overflowed => Err(CommonHitError::Overflow),
+        None => Err(CommonHitError::NoCommonHit),
+    }
+}
+
+/// An error which can be returned by [`first_common_hit`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommonHitError {
+    NoSequences,
+    /// The sequences are never in a matching state at the same step.
+    NoCommonHit,
+    Overflow,
+}
+
+impl Error for CommonHitError {}
+
+impl Display for CommonHitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommonHitError::NoSequences => write!(f, "no sequences were passed."),
+            CommonHitError::NoCommonHit => {
+                write!(f, "the sequences never match at the same step.")
+            }
+            CommonHitError::Overflow => write!(f, "the first common step does not fit into u64."),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_cycle, first_common_hit, CommonHitError, Hits};
+
+    fn hits(offset: u64, len: u64, prefix: &[u64], cycle: &[u64]) -> Hits {
+        Hits {
+            offset,
+            len,
+            prefix: prefix.to_vec(),
+            cycle: cycle.to_vec(),
+        }
+    }
+
+    #[test]
+    fn finds_cycle() {
+        let next = |x: &u32| (x * x + 1) % 255;
+        let cycle = find_cycle(3, next);
+        assert_eq!(cycle.states.len(), cycle.offset + cycle.len);
+
+        let mut state = 3;
+        for step in 0..1000 {
+            assert_eq!(*cycle.state_at(step), state);
+            state = next(&state);
+        }
+    }
+
+    #[test]
+    fn collects_hits() {
+        // 0, 1, 2, 3, 4, 2, 3, 4, ...
+        let cycle = find_cycle(0, |x| if *x == 4 { 2 } else { x + 1 });
+        let hits = cycle.hits(|x| x % 2 == 0);
+        assert_eq!(hits, self::hits(2, 3, &[0], &[2, 4]));
+        assert!(hits.contains(0));
+        assert!(!hits.contains(1));
+        assert!(hits.contains(7));
+        assert!(!hits.contains(9));
+    }
+
+    #[test]
+    fn finds_first_common_hit() {
+        // the lcm shortcut holds.
+        let a = hits(1, 2, &[], &[2]);
+        let b = hits(1, 3, &[], &[3]);
+        assert!(a.is_lcm_compatible() && b.is_lcm_compatible());
+        assert_eq!(first_common_hit(&[a.clone(), b.clone()]), Ok(6));
+
+        // offsets and multiple hits per cycle.
+        let c = hits(4, 5, &[1, 3], &[5, 7]);
+        let d = hits(0, 4, &[], &[1, 3]);
+        assert!(!c.is_lcm_compatible());
+        assert_eq!(first_common_hit(&[c.clone(), d.clone()]), Ok(1));
+        assert_eq!(first_common_hit(&[b, c, d]), Ok(3));
+
+        // both hit on even steps only in different phases.
+        let e = hits(0, 2, &[], &[0]);
+        let f = hits(0, 4, &[], &[1]);
+        assert_eq!(first_common_hit(&[e, f]), Err(CommonHitError::NoCommonHit));
+        assert_eq!(first_common_hit(&[]), Err(CommonHitError::NoSequences));
+    }
+
+    #[test]
+    fn first_common_hit_matches_brute_force() {
+        let sequences = [
+            hits(3, 4, &[0, 2], &[4, 6]),
+            hits(0, 6, &[], &[0, 2, 5]),
+            hits(5, 3, &[2], &[6]),
+        ];
+        let expected = (0..1000).find(|step| sequences.iter().all(|x| x.contains(*step)));
+        assert_eq!(first_common_hit(&sequences).ok(), expected);
+    }
+}
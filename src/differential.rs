@@ -0,0 +1,490 @@
+//! Differential testing: several implementations of the same puzzle part must agree on every input.
+//!
+//! Optimized solutions often keep a slow reference implementation around. [`Variants`] runs all of them on
+//! generated or randomly mutated inputs, and shrinks the first input they disagree on before reporting it.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+
+use crate::generators::InputGenerator;
+use crate::rng::Rng;
+use crate::Answer;
+
+/// The number of mutated inputs that are derived from an input for every seed.
+const MUTANTS: usize = 8;
+
+/// What an implementation returned for an input.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Answer(Option<Answer>),
+    /// The implementation panicked with this message.
+    Panic(String),
+}
+
+impl Outcome {
+    /// Returns `true` if both outcomes are the same answer, or if both implementations panicked.
+    #[must_use]
+    pub fn agrees_with(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Answer(a), Outcome::Answer(b)) => a == b,
+            (Outcome::Panic(_), Outcome::Panic(_)) => true,
+            _ => false,
+        }
+    }
+
+    #[must_use]
+    pub fn is_panic(&self) -> bool {
+        matches!(self, Outcome::Panic(_))
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(Some(answer)) => write!(f, "{answer}"),
+            Outcome::Answer(None) => write!(f, "no answer"),
+            Outcome::Panic(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// The first input that implementations disagreed on, after shrinking it.
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    /// The fuzz seed that produced the input, if it came from a fuzz run.
+    pub seed: Option<u64>,
+    pub input: String,
+    /// The outcome of every implementation, in the order they were added.
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl Error for Disagreement {}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.seed {
+            Some(seed) => writeln!(f, "implementations disagree on an input from seed {seed}:")?,
+            None => writeln!(f, "implementations disagree on an input:")?,
+        }
+        writeln!(f, "{}", self.input.trim_end())?;
+        for (name, outcome) in &self.outcomes {
+            write!(f, "\n  {name}: {outcome}")?;
+        }
+        Ok(())
+    }
+}
+
+type Solver<'a> = Box<dyn Fn(&str) -> Option<Answer> + 'a>;
+
+/// Implementations of the same puzzle part, which must return the same answer for every input.
+///
+/// ```
+/// # use advent_of_code::differential::Variants;
+/// let variants = Variants::new()
+///     .with("sum", |input: &str| Some(input.lines().count() * 2))
+///     .with("doubled", |input: &str| Some(input.lines().map(|_| 2_u32).sum::<u32>()));
+/// assert!(variants.check("a\nb\n").is_ok());
+/// assert!(variants.fuzz_mutated("1 2\n3 4\n", 0..20).is_ok());
+/// ```
+#[derive(Default)]
+pub struct Variants<'a> {
+    solvers: Vec<(&'static str, Solver<'a>)>,
+}
+
+impl<'a> Variants<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an implementation. Answers are compared as [`Answer`]s, so implementations may return different types.
+    #[must_use]
+    pub fn with<T: Into<Answer>>(
+        mut self,
+        name: &'static str,
+        solver: impl Fn(&str) -> Option<T> + 'a,
+    ) -> Self {
+        self.solvers
+            .push((name, Box::new(move |input| solver(input).map(Into::into))));
+        self
+    }
+
+    /// Runs every implementation on `input`. Panics are caught and returned as [`Outcome::Panic`].
+    #[must_use]
+    pub fn run(&self, input: &str) -> Vec<(&'static str, Outcome)> {
+        self.solvers
+            .iter()
+            .map(|(name, solver)| {
+                let outcome = match panic::catch_unwind(AssertUnwindSafe(|| solver(input))) {
+                    Ok(answer) => Outcome::Answer(answer),
+                    Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+                };
+                (*name, outcome)
+            })
+            .collect()
+    }
+
+    /// Returns the outcomes of `input` if the implementations disagree.
+    fn disagreement(&self, input: &str) -> Option<Vec<(&'static str, Outcome)>> {
+        let outcomes = self.run(input);
+        let (_, first) = outcomes.first()?;
+        if outcomes.iter().all(|(_, x)| x.agrees_with(first)) {
+            None
+        } else {
+            Some(outcomes)
+        }
+    }
+
+    /// Checks that all implementations agree on `input`, shrinking it if they do not.
+    pub fn check(&self, input: &str) -> Result<(), Disagreement> {
+        without_panic_messages(|| match self.disagreement(input) {
+            Some(outcomes) => Err(self.shrink(None, input, &outcomes)),
+            None => Ok(()),
+        })
+    }
+
+    /// Checks the input generated for every seed with `size`.
+    /// Generated inputs are valid, so an implementation that panics on one disagrees with the others.
+    pub fn fuzz_generated(
+        &self,
+        generator: &dyn InputGenerator,
+        size: usize,
+        seeds: Range<u64>,
+    ) -> Result<(), Disagreement> {
+        without_panic_messages(|| {
+            for seed in seeds {
+                let input = generator.generate(seed, size);
+                if let Some(outcomes) = self.disagreement(&input) {
+                    return Err(self.shrink(Some(seed), &input, &outcomes));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Checks inputs mutated from `input`, e.g. an example, for every seed.
+    ///
+    /// Mutated inputs may not be valid, so they are skipped if any implementation panics.
+    /// Implementations must not loop forever on invalid inputs.
+    pub fn fuzz_mutated(&self, input: &str, seeds: Range<u64>) -> Result<(), Disagreement> {
+        without_panic_messages(|| {
+            seeds
+                .into_iter()
+                .try_for_each(|seed| self.check_mutants(seed, input))
+        })
+    }
+
+    fn check_mutants(&self, seed: u64, input: &str) -> Result<(), Disagreement> {
+        let mut rng = Rng::new(seed);
+        for _ in 0..MUTANTS {
+            let mutant = mutate(input, &mut rng);
+            if let Some(outcomes) = self.disagreement(&mutant) {
+                if !outcomes.iter().any(|(_, x)| x.is_panic()) {
+                    return Err(self.shrink(Some(seed), &mutant, &outcomes));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Shrinks an input the implementations disagree on, by removing lines and making numbers smaller.
+    ///
+    /// A smaller input is only kept if the implementations still disagree and the same implementations panic,
+    /// so the shrunk input shows the same bug rather than just being invalid.
+    fn shrink(
+        &self,
+        seed: Option<u64>,
+        input: &str,
+        outcomes: &[(&'static str, Outcome)],
+    ) -> Disagreement {
+        let panics: Vec<bool> = outcomes.iter().map(|(_, x)| x.is_panic()).collect();
+        let fails = |candidate: &str| {
+            self.disagreement(candidate).is_some_and(|x| {
+                x.iter()
+                    .map(|(_, x)| x.is_panic())
+                    .eq(panics.iter().copied())
+            })
+        };
+
+        let mut input = input.to_string();
+        loop {
+            let shrunk = shrink_numbers(&remove_lines(&input, fails), fails);
+            if shrunk == input {
+                break;
+            }
+            input = shrunk;
+        }
+
+        Disagreement {
+            seed,
+            outcomes: self.run(&input),
+            input,
+        }
+    }
+}
+
+/// Runs `f` with a panic hook that ignores panics on the current thread, so the panics that [`Variants::run`] catches
+/// do not print a message for every input. Panics on other threads, e.g. of tests running in parallel, are still
+/// reported by the previous hook, which is restored afterwards.
+fn without_panic_messages<R>(f: impl FnOnce() -> R) -> R {
+    let thread = thread::current().id();
+    let previous = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().id() != thread {
+            hook(info);
+        }
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+    drop(panic::take_hook());
+    match Arc::try_unwrap(previous) {
+        Ok(previous) => panic::set_hook(previous),
+        // another thread replaced the hook in the meantime and still holds on to the silent one.
+        Err(previous) => panic::set_hook(Box::new(move |info| previous(info))),
+    }
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    lines.iter().map(|x| format!("{x}\n")).collect()
+}
+
+/// Removes chunks of lines while `fails` holds, halving the chunk size when no chunk can be removed.
+fn remove_lines(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+            if fails(&join_lines(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    join_lines(&lines)
+}
+
+/// Replaces every number with a smaller one for which `fails` holds, found by binary search.
+fn shrink_numbers(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut input = input.to_string();
+    let mut start = 0;
+
+    while let Some(offset) = input[start..].find(|c: char| c.is_ascii_digit()) {
+        let begin = start + offset;
+        let end = input[begin..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(input.len(), |x| begin + x);
+
+        let Ok(value) = input[begin..end].parse::<u128>() else {
+            start = end;
+            continue;
+        };
+        let replace = |x: u128| format!("{}{x}{}", &input[..begin], &input[end..]);
+
+        // `hi` always fails. The search assumes that numbers above a failing one fail too, which is not guaranteed,
+        // but the result is a failing input either way.
+        let (mut lo, mut hi) = (0, value);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if fails(&replace(mid)) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        let shrunk = hi.to_string();
+        input = replace(hi);
+        start = begin + shrunk.len();
+    }
+
+    input
+}
+
+/// Applies one to three random edits to the lines of `input`: removing, duplicating or swapping lines, or replacing a digit.
+#[must_use]
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<String> = input.lines().map(String::from).collect();
+    if lines.is_empty() {
+        return input.to_string();
+    }
+
+    for _ in 0..rng.range(1..4) {
+        let i = rng.index(lines.len());
+        match rng.below(4) {
+            0 if lines.len() > 1 => {
+                lines.remove(i);
+            }
+            1 => lines.insert(i, lines[i].clone()),
+            2 => {
+                let j = rng.index(lines.len());
+                lines.swap(i, j);
+            }
+            _ => {
+                let digits: Vec<usize> = lines[i]
+                    .char_indices()
+                    .filter(|(_, c)| c.is_ascii_digit())
+                    .map(|(pos, _)| pos)
+                    .collect();
+                if !digits.is_empty() {
+                    let pos = *rng.choose(&digits);
+                    let digit = char::from(b'0' + rng.below(10) as u8);
+                    lines[i].replace_range(pos..=pos, &digit.to_string());
+                }
+            }
+        }
+    }
+
+    lines.iter().map(|x| format!("{x}\n")).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mutate, Outcome, Variants};
+    use crate::generators::Day09;
+    use crate::rng::Rng;
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    fn sum(input: &str) -> Option<u64> {
+        Some(
+            input
+                .split_ascii_whitespace()
+                .filter_map(|x| x.parse::<u64>().ok())
+                .sum(),
+        )
+    }
+
+    /// Wrong for numbers above 99 in inputs with more than two lines.
+    fn buggy_sum(input: &str) -> Option<String> {
+        let numbers = input
+            .split_ascii_whitespace()
+            .filter_map(|x| x.parse::<u64>().ok());
+        if input.lines().count() > 2 {
+            Some(numbers.map(|x| x.min(99)).sum::<u64>().to_string())
+        } else {
+            Some(numbers.sum::<u64>().to_string())
+        }
+    }
+
+    #[test]
+    fn agrees_across_answer_types() {
+        let variants = Variants::new()
+            .with("sum", sum)
+            .with("text", |input: &str| sum(input).map(|x| x.to_string()));
+        assert!(variants.check("1 2\n3\n").is_ok());
+        assert!(variants.fuzz_generated(&Day09, 10, 0..5).is_ok());
+        assert!(variants.fuzz_mutated("10 20\n30\n", 0..50).is_ok());
+    }
+
+    #[test]
+    fn shrinks_first_disagreement() {
+        let variants = Variants::new().with("sum", sum).with("buggy", buggy_sum);
+        let input = "1 2 3\n4 150 6\n7 8 9\n10 11 12\n13 14 15\n";
+        let disagreement = variants.check(input).unwrap_err();
+
+        // three lines and a single number above 99 are needed, and `100` is the smallest one.
+        assert_eq!(disagreement.input, "0 0 0\n0 100 0\n0 0 0\n");
+        assert_eq!(disagreement.seed, None);
+        let outcomes: Vec<String> = disagreement
+            .outcomes
+            .iter()
+            .map(|(_, x)| x.to_string())
+            .collect();
+        assert_eq!(outcomes, ["100", "99"]);
+        assert!(disagreement.to_string().contains("buggy: 99"));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let variants = Variants::new()
+            .with("sum", sum)
+            .with("strict", |input: &str| {
+                assert!(!input.contains('7'), "unlucky number");
+                sum(input)
+            });
+
+        // a panic on a generated input is a disagreement.
+        let disagreement = variants.fuzz_generated(&Day09, 5, 0..10).unwrap_err();
+        assert!(disagreement.seed.is_some());
+        assert!(disagreement.input.contains('7'));
+        assert!(disagreement.outcomes[1].1.is_panic());
+        assert!(matches!(&disagreement.outcomes[1].1, Outcome::Panic(x) if x == "unlucky number"));
+
+        // mutated inputs that make an implementation panic are skipped.
+        assert!(variants.fuzz_mutated("1 2\n3 4\n", 0..50).is_ok());
+    }
+
+    #[test]
+    fn silences_panics_only_while_fuzzing() {
+        let thread = thread::current().id();
+        let reported = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&reported);
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().id() == thread {
+                counter.fetch_add(1, Ordering::SeqCst);
+            } else {
+                previous(info);
+            }
+        }));
+
+        let variants = Variants::new()
+            .with("sum", sum)
+            .with("panics", |_: &str| -> Option<u64> { panic!("always") });
+        let disagreement = variants.check("1 2\n3\n");
+        let after = panic::catch_unwind(|| panic!("after fuzzing"));
+        drop(panic::take_hook());
+
+        assert!(disagreement.is_err());
+        assert!(after.is_err());
+        assert_eq!(reported.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn mutates_deterministically() {
+        let input = "seeds: 79 14\n\nmap:\n50 98 2\n";
+        let mutants: Vec<String> = (0..20).map(|x| mutate(input, &mut Rng::new(x))).collect();
+        assert_eq!(
+            mutants,
+            (0..20)
+                .map(|x| mutate(input, &mut Rng::new(x)))
+                .collect::<Vec<_>>()
+        );
+        assert!(mutants.iter().any(|x| x != input));
+        assert!(mutants.iter().all(|x| x.ends_with('\n')));
+        assert_eq!(mutate("", &mut Rng::new(0)), "");
+    }
+}
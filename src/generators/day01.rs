@@ -0,0 +1,35 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Calibration lines of letters, digits and spelled-out digits. Every line has at least one digit.
+/// `size` is the number of lines.
+pub struct Day01;
+
+impl InputGenerator for Day01 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let parts = rng.range(1..6);
+            let digit_at = rng.below(parts);
+            for i in 0..parts {
+                if i == digit_at || rng.chance(1, 4) {
+                    input.push(char::from(b'1' + rng.below(9) as u8));
+                } else if rng.chance(1, 3) {
+                    input.push_str(WORDS[rng.index(WORDS.len())]);
+                } else {
+                    (0..rng.range(1..5))
+                        .for_each(|_| input.push(char::from(b'a' + rng.below(26) as u8)));
+                }
+            }
+            input.push('\n');
+        }
+
+        input
+    }
+}
@@ -0,0 +1,29 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+/// Games with draws of up to 20 red, green and blue cubes. `size` is the number of games.
+pub struct Day02;
+
+impl InputGenerator for Day02 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for id in 1..=size {
+            let draws: Vec<String> = (0..rng.range(1..7))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    colors[..rng.index(3) + 1]
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.range(1..21)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            input.push_str(&format!("Game {id}: {}\n", draws.join("; ")));
+        }
+
+        input
+    }
+}
@@ -0,0 +1,38 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+/// A square engine schematic of part numbers and symbols. `size` is the width and height.
+pub struct Day03;
+
+impl InputGenerator for Day03 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let mut line = String::new();
+            while line.len() < size {
+                match rng.below(10) {
+                    0 | 1 => {
+                        let number = rng.range(1..1000).to_string();
+                        if line.len() + number.len() <= size {
+                            line.push_str(&number);
+                        }
+                        // numbers in the same row are never adjacent.
+                        if line.len() < size {
+                            line.push('.');
+                        }
+                    }
+                    2 => line.push(*rng.choose(&SYMBOLS)),
+                    _ => line.push('.'),
+                }
+            }
+            input.push_str(&line);
+            input.push('\n');
+        }
+
+        input
+    }
+}
@@ -0,0 +1,58 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+const WINNING: usize = 10;
+const PICKED: usize = 25;
+/// Cards with more copies than this do not win any cards.
+const MAX_WINNING_COPIES: u64 = 1 << 28;
+
+/// Scratchcards with 10 winning and 25 picked numbers. `size` is the number of cards.
+///
+/// Cards never win copies of cards past the end of the table, and cards with many copies win nothing,
+/// so the total number of cards fits into `u64`.
+pub struct Day04;
+
+impl InputGenerator for Day04 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+        let width = size.to_string().len();
+        let mut copies = vec![1_u64; size];
+
+        for num in 1..=size {
+            let mut numbers: Vec<u32> = (1..100).collect();
+            rng.shuffle(&mut numbers);
+            let (winning, others) = numbers.split_at(WINNING);
+
+            let matches = if copies[num - 1] > MAX_WINNING_COPIES {
+                0
+            } else {
+                rng.index(WINNING.min(size - num) + 1)
+            };
+            for i in num..num + matches {
+                copies[i] += copies[num - 1];
+            }
+            let mut picked: Vec<u32> = winning[..matches]
+                .iter()
+                .chain(&others[..PICKED - matches])
+                .copied()
+                .collect();
+            rng.shuffle(&mut picked);
+
+            let format = |numbers: &[u32]| {
+                numbers
+                    .iter()
+                    .map(|x| format!("{x:>2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            input.push_str(&format!(
+                "Card {num:>width$}: {} | {}\n",
+                format(winning),
+                format(&picked)
+            ));
+        }
+
+        input
+    }
+}
@@ -0,0 +1,47 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Values are in `0..2^32`, like in the real inputs.
+const LIMIT: u64 = 1 << 32;
+
+/// Seed ranges and seven almanac maps with non-overlapping source ranges.
+/// `size` is the number of seed ranges and the number of ranges in every map.
+pub struct Day05;
+
+impl InputGenerator for Day05 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let count = size.clamp(1, 1 << 20) as u64;
+        let step = LIMIT / count;
+
+        let seeds: Vec<String> = (0..count)
+            .map(|_| format!("{} {}", rng.below(LIMIT), rng.range(1..LIMIT / 16)))
+            .collect();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+        for name in MAPS {
+            // every source range starts in its own slice of `0..LIMIT`, so sources never overlap.
+            let mut lines: Vec<String> = (0..count)
+                .map(|i| {
+                    let src = i * step + rng.below(step / 2 + 1);
+                    let len = rng.range(1..step / 2 + 1);
+                    format!("{} {src} {len}", rng.below(LIMIT))
+                })
+                .collect();
+            rng.shuffle(&mut lines);
+            input.push_str(&format!("\n{name} map:\n{}\n", lines.join("\n")));
+        }
+
+        input
+    }
+}
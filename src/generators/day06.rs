@@ -0,0 +1,24 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+/// Boat races that can all be won. `size` is the number of races, at most 4,
+/// because part two joins all numbers into a single integer.
+pub struct Day06;
+
+impl InputGenerator for Day06 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let races: Vec<(u64, u64)> = (0..size.clamp(1, 4))
+            .map(|_| {
+                let time = rng.range(7..100);
+                let best = (time / 2) * (time - time / 2);
+                (time, rng.below(best))
+            })
+            .collect();
+
+        // every value fits into a column of width 5.
+        let times: String = races.iter().map(|(time, _)| format!("{time:>5}")).collect();
+        let distances: String = races.iter().map(|(_, dist)| format!("{dist:>5}")).collect();
+        format!("Time:    {times}\nDistance:{distances}\n")
+    }
+}
@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use super::InputGenerator;
+use crate::cards::CAMEL_CARDS;
+use crate::rng::Rng;
+
+/// Distinct Camel Cards hands with bids up to 1000. `size` is the number of hands.
+pub struct Day07;
+
+impl InputGenerator for Day07 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let labels: Vec<char> = CAMEL_CARDS.chars().collect();
+        let mut seen = HashSet::new();
+        let mut input = String::new();
+
+        // there are only 13^5 distinct hands.
+        while seen.len() < size.min(labels.len().pow(5)) {
+            let hand: String = (0..5).map(|_| *rng.choose(&labels)).collect();
+            if seen.insert(hand.clone()) {
+                input.push_str(&format!("{hand} {}\n", rng.range(1..1001)));
+            }
+        }
+
+        input
+    }
+}
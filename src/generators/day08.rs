@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use super::InputGenerator;
+use crate::rng::Rng;
+
+/// Chain lengths are the instruction count times these primes, so all ghosts meet within `u64`.
+const PRIMES: [usize; 6] = [3, 5, 7, 11, 13, 17];
+
+/// Instructions and a network in which every ghost walks a chain from its `A` node to its `Z` node and then loops.
+///
+/// Ghost 0 walks from `AAA` to `ZZZ`. The other nodes are decoys that are never reached.
+/// `size` scales the number of nodes, the instruction count and the number of ghosts.
+pub struct Day08;
+
+struct Labels {
+    seen: HashSet<String>,
+}
+
+impl Labels {
+    /// Returns an unused three-letter label ending with `last`, or with any letter but `A` and `Z`.
+    fn next(&mut self, rng: &mut Rng, last: Option<char>) -> String {
+        loop {
+            let mut label: String = (0..2)
+                .map(|_| char::from(b'A' + rng.below(26) as u8))
+                .collect();
+            label.push(last.unwrap_or_else(|| char::from(b'B' + rng.below(24) as u8)));
+            if self.seen.insert(label.clone()) {
+                return label;
+            }
+        }
+    }
+}
+
+impl InputGenerator for Day08 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut labels = Labels {
+            seen: HashSet::from(["AAA".to_string(), "ZZZ".to_string()]),
+        };
+        let mut lines = vec![];
+
+        let instructions: String = (0..rng.range(2..(size as u64 / 10).clamp(2, 100) + 1))
+            .map(|_| *rng.choose(&['L', 'R']))
+            .collect();
+        let len = instructions.len();
+
+        for (ghost, prime) in PRIMES.iter().take(1 + size / 100).enumerate() {
+            let (start, end) = if ghost == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            } else {
+                (
+                    labels.next(&mut rng, Some('A')),
+                    labels.next(&mut rng, Some('Z')),
+                )
+            };
+
+            // both edges lead along the chain, so the ghost is on `end` exactly every `len * prime` steps.
+            let chain: Vec<String> = (1..len * prime)
+                .map(|_| labels.next(&mut rng, None))
+                .collect();
+            lines.push(format!("{start} = ({0}, {0})", chain[0]));
+            for pair in chain.windows(2) {
+                lines.push(format!("{} = ({1}, {1})", pair[0], pair[1]));
+            }
+            lines.push(format!("{} = ({end}, {end})", chain[chain.len() - 1]));
+            lines.push(format!("{end} = ({0}, {0})", chain[0]));
+        }
+
+        let decoys: Vec<String> = (lines.len()..size.min(5000))
+            .map(|_| labels.next(&mut rng, None))
+            .collect();
+        for decoy in &decoys {
+            lines.push(format!(
+                "{decoy} = ({}, {})",
+                rng.choose(&decoys),
+                rng.choose(&decoys)
+            ));
+        }
+
+        rng.shuffle(&mut lines);
+        format!("{instructions}\n\n{}\n", lines.join("\n"))
+    }
+}
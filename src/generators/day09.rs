@@ -0,0 +1,27 @@
+use super::InputGenerator;
+use crate::rng::Rng;
+
+const VALUES: i64 = 21;
+
+/// Histories of 21 values of polynomials up to degree 6. `size` is the number of histories.
+pub struct Day09;
+
+impl InputGenerator for Day09 {
+    fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let coefficients: Vec<i64> =
+                (0..=rng.below(7)).map(|_| rng.range_i64(-9..10)).collect();
+            let values: Vec<String> = (0..VALUES)
+                .map(|x| coefficients.iter().rev().fold(0, |acc, c| acc * x + c))
+                .map(|x| x.to_string())
+                .collect();
+            input.push_str(&values.join(" "));
+            input.push('\n');
+        }
+
+        input
+    }
+}
@@ -0,0 +1,96 @@
+//! Generators for syntactically valid puzzle inputs, to test solutions on more than one personal input.
+//!
+//! Every generator is deterministic: the same seed and size always produce the same input.
+
+use crate::Day;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+
+pub use day01::Day01;
+pub use day02::Day02;
+pub use day03::Day03;
+pub use day04::Day04;
+pub use day05::Day05;
+pub use day06::Day06;
+pub use day07::Day07;
+pub use day08::Day08;
+pub use day09::Day09;
+
+/// Generates inputs for one day.
+pub trait InputGenerator {
+    /// Returns an input generated from `seed`. `size` scales the input, e.g. the number of lines.
+    fn generate(&self, seed: u64, size: usize) -> String;
+}
+
+/// Returns the input generator for `day`, if there is one.
+#[must_use]
+pub fn for_day(day: Day) -> Option<&'static dyn InputGenerator> {
+    match day.into_inner() {
+        1 => Some(&Day01),
+        2 => Some(&Day02),
+        3 => Some(&Day03),
+        4 => Some(&Day04),
+        5 => Some(&Day05),
+        6 => Some(&Day06),
+        7 => Some(&Day07),
+        8 => Some(&Day08),
+        9 => Some(&Day09),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{for_day, Day05, InputGenerator};
+    use crate::Day;
+
+    #[test]
+    fn generates_deterministic_inputs() {
+        for day in 1..=9 {
+            let generator = for_day(Day::new(day).unwrap()).unwrap();
+            let input = generator.generate(1, 20);
+            assert!(input.ends_with('\n'), "day {day}");
+            assert_eq!(input, generator.generate(1, 20), "day {day}");
+            assert_ne!(input, generator.generate(2, 20), "day {day}");
+        }
+        assert!(for_day(Day::new(25).unwrap()).is_none());
+    }
+
+    #[test]
+    fn generates_disjoint_day05_sources() {
+        for size in 1..=8 {
+            for seed in 0..200 {
+                let input = Day05.generate(seed, size);
+                for block in input.split("\n\n").skip(1) {
+                    let mut sources: Vec<(u64, u64)> = block
+                        .lines()
+                        .skip(1)
+                        .map(|line| {
+                            let x: Vec<u64> = line.split(' ').map(|x| x.parse().unwrap()).collect();
+                            (x[1], x[1] + x[2])
+                        })
+                        .collect();
+                    sources.sort_unstable();
+                    assert!(
+                        sources.windows(2).all(|w| w[0].1 <= w[1].0),
+                        "seed {seed}, size {size}"
+                    );
+                    assert!(
+                        sources.last().unwrap().1 <= 1 << 32,
+                        "seed {seed}, size {size}"
+                    );
+                }
+            }
+        }
+    }
+}
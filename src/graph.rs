@@ -0,0 +1,473 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+
+/// Identifies a node in a [`Graph`]. Ids are assigned in the order labels are first seen, starting at `0`.
+pub type NodeId = usize;
+
+/// An outgoing edge of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub to: NodeId,
+    pub weight: u64,
+}
+
+/// A graph with weighted edges whose nodes are labeled by strings.
+///
+/// Labels are interned, so nodes are referred to by compact [`NodeId`]s and can be used as vector indices.
+/// Edges keep their insertion order, e.g. left before right for `AAA = (BBB, CCC)`.
+///
+/// ```
+/// # use advent_of_code::graph::Graph;
+/// let graph = Graph::parse_pairs("AAA = (BBB, CCC)\nBBB = (CCC, CCC)\n").unwrap();
+/// let aaa = graph.id("AAA").unwrap();
+/// let ccc = graph.id("CCC").unwrap();
+/// assert_eq!(graph.label(graph.edges(aaa)[0].to), "BBB");
+/// assert_eq!(graph.bfs(aaa)[ccc], Some(1));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    ids: HashMap<String, NodeId>,
+    labels: Vec<String>,
+    edges: Vec<Vec<Edge>>,
+    undirected: bool,
+}
+
+impl Graph {
+    /// Creates an empty graph where every edge only points from one node to another.
+    #[must_use]
+    pub fn directed() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty graph where every edge is added in both directions.
+    #[must_use]
+    pub fn undirected() -> Self {
+        Self {
+            undirected: true,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn is_directed(&self) -> bool {
+        !self.undirected
+    }
+
+    /// Returns the id for `label`, adding a node if it does not exist yet.
+    pub fn add_node(&mut self, label: &str) -> NodeId {
+        if let Some(id) = self.ids.get(label) {
+            return *id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.to_string(), id);
+        self.labels.push(label.to_string());
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Adds an edge between two labeled nodes, adding the nodes if necessary. Returns their ids.
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: u64) -> (NodeId, NodeId) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.add_edge_by_id(from, to, weight);
+        (from, to)
+    }
+
+    /// Adds an edge between two existing nodes.
+    ///
+    /// # Panics
+    /// Panics if either node does not exist.
+    pub fn add_edge_by_id(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        assert!(to < self.len(), "node {to} does not exist");
+        self.edges[from].push(Edge { to, weight });
+        if self.undirected && from != to {
+            self.edges[to].push(Edge { to: from, weight });
+        }
+    }
+
+    /// Returns the id of the node with `label`.
+    #[must_use]
+    pub fn id(&self, label: &str) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    /// Returns the label of a node.
+    ///
+    /// # Panics
+    /// Panics if the node does not exist.
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &str {
+        &self.labels[id]
+    }
+
+    /// The number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Iterates all node ids.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len()
+    }
+
+    /// Returns the outgoing edges of a node in insertion order.
+    #[must_use]
+    pub fn edges(&self, id: NodeId) -> &[Edge] {
+        &self.edges[id]
+    }
+
+    /// Iterates the nodes that the edges of a node point to.
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|edge| edge.to)
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Returns the number of edges on a shortest path from `start` to every node, ignoring weights.
+    #[must_use]
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        dist[start] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let next_dist = dist[node].map(|x| x + 1);
+            for next in self.neighbors(node) {
+                if dist[next].is_none() {
+                    dist[next] = next_dist;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// Returns the nodes reachable from `start` in depth-first pre-order, following edges in insertion order.
+    #[must_use]
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            stack.extend(
+                self.edges[node]
+                    .iter()
+                    .rev()
+                    .map(|edge| edge.to)
+                    .filter(|x| !visited[*x]),
+            );
+        }
+
+        order
+    }
+
+    /// Returns the total weight of a shortest path from `start` to every node.
+    #[must_use]
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<u64>> {
+        let mut dist = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if dist[node].is_some() {
+                continue;
+            }
+            dist[node] = Some(cost);
+            for edge in &self.edges[node] {
+                if dist[edge.to].is_none() {
+                    heap.push(Reverse((cost + edge.weight, edge.to)));
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// Returns the total weight and the nodes of a shortest path from `start` to `goal`.
+    ///
+    /// `heuristic` estimates the remaining weight from a node to `goal`. It must never overestimate,
+    /// a heuristic that always returns `0` turns this into Dijkstra's algorithm.
+    pub fn astar(
+        &self,
+        start: NodeId,
+        goal: NodeId,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut best = vec![u64::MAX; self.len()];
+        let mut parent = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+        best[start] = 0;
+
+        while let Some(Reverse((_, cost, node))) = heap.pop() {
+            if node == goal {
+                let mut path = vec![goal];
+                while let Some(prev) = parent[*path.last().unwrap()] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+            if cost > best[node] {
+                continue;
+            }
+            for edge in &self.edges[node] {
+                let next_cost = cost + edge.weight;
+                if next_cost < best[edge.to] {
+                    best[edge.to] = next_cost;
+                    parent[edge.to] = Some(node);
+                    heap.push(Reverse((
+                        next_cost + heuristic(edge.to),
+                        next_cost,
+                        edge.to,
+                    )));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Orders the nodes so that every edge points from an earlier to a later node.
+    /// Returns [`None`] if the graph has a cycle, which includes every undirected graph with edges.
+    #[must_use]
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree = vec![0; self.len()];
+        self.edges
+            .iter()
+            .flatten()
+            .for_each(|edge| in_degree[edge.to] += 1);
+
+        let mut queue: VecDeque<NodeId> = self.nodes().filter(|x| in_degree[*x] == 0).collect();
+        let mut order = vec![];
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Returns the strongly connected components of the graph, using Kosaraju's algorithm.
+    /// Components are ordered so that edges between components only point to later components.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        // 1. order the nodes by the time a depth-first search finishes them.
+        let mut visited = vec![false; self.len()];
+        let mut finished = vec![];
+
+        for root in self.nodes() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+
+            while let Some((node, edge_index)) = stack.pop() {
+                if let Some(edge) = self.edges[node].get(edge_index) {
+                    stack.push((node, edge_index + 1));
+                    if !visited[edge.to] {
+                        visited[edge.to] = true;
+                        stack.push((edge.to, 0));
+                    }
+                } else {
+                    finished.push(node);
+                }
+            }
+        }
+
+        // 2. collect the nodes reachable via reversed edges, starting with the last finished node.
+        let mut reversed = vec![vec![]; self.len()];
+        for node in self.nodes() {
+            for next in self.neighbors(node) {
+                reversed[next].push(node);
+            }
+        }
+
+        let mut component_of = vec![None; self.len()];
+        let mut components = vec![];
+
+        for root in finished.into_iter().rev() {
+            if component_of[root].is_some() {
+                continue;
+            }
+            let id = components.len();
+            let mut component = vec![];
+            let mut stack = vec![root];
+            component_of[root] = Some(id);
+
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for &prev in &reversed[node] {
+                    if component_of[prev].is_none() {
+                        component_of[prev] = Some(id);
+                        stack.push(prev);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Parses a directed graph from lines like `AAA = (BBB, CCC)`, with an edge of weight `1` to every listed node.
+    pub fn parse_pairs(input: &str) -> Result<Self, GraphParseError> {
+        let mut graph = Self::directed();
+
+        for (i, line) in input.lines().enumerate() {
+            let error = || GraphParseError {
+                line: i + 1,
+                content: line.to_string(),
+            };
+
+            let (from, to) = line.split_once(" = ").ok_or_else(error)?;
+            let to = to
+                .trim()
+                .strip_prefix('(')
+                .and_then(|x| x.strip_suffix(')'))
+                .ok_or_else(error)?;
+
+            let from = graph.add_node(from.trim());
+            for label in to.split(',').map(str::trim) {
+                if label.is_empty() {
+                    return Err(error());
+                }
+                let to = graph.add_node(label);
+                graph.add_edge_by_id(from, to, 1);
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// An error which can be returned when parsing a [`Graph`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct GraphParseError {
+    /// The line number, starting at `1`.
+    pub line: usize,
+    pub content: String,
+}
+
+impl Error for GraphParseError {}
+
+impl Display for GraphParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: expecting `NODE = (NODE, ...)`, found `{}`",
+            self.line, self.content
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Graph, GraphParseError};
+
+    fn labels(graph: &Graph, ids: &[usize]) -> Vec<String> {
+        ids.iter().map(|x| graph.label(*x).to_string()).collect()
+    }
+
+    #[test]
+    fn parses_pairs() {
+        let graph = Graph::parse_pairs("AAA = (BBB, CCC)\nBBB = (DDD, EEE)\n").unwrap();
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.id("AAA"), Some(0));
+        assert_eq!(graph.id("ZZZ"), None);
+        let neighbors: Vec<_> = graph.neighbors(1).collect();
+        assert_eq!(labels(&graph, &neighbors), ["DDD", "EEE"]);
+        assert_eq!(
+            Graph::parse_pairs("AAA = (BBB, CCC)\nBBB = BBB").unwrap_err(),
+            GraphParseError {
+                line: 2,
+                content: "BBB = BBB".into()
+            }
+        );
+    }
+
+    #[test]
+    fn traverses() {
+        let mut graph = Graph::undirected();
+        graph.add_edge("a", "b", 1);
+        graph.add_edge("a", "c", 1);
+        graph.add_edge("b", "d", 1);
+        graph.add_node("e");
+        assert_eq!(graph.bfs(0), [Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(labels(&graph, &graph.dfs(0)), ["a", "b", "d", "c"]);
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let mut graph = Graph::directed();
+        graph.add_edge("a", "b", 7);
+        graph.add_edge("a", "c", 2);
+        graph.add_edge("c", "b", 3);
+        graph.add_edge("b", "d", 1);
+        graph.add_node("e");
+        assert_eq!(
+            graph.dijkstra(0),
+            [Some(0), Some(5), Some(2), Some(6), None]
+        );
+
+        let (cost, path) = graph.astar(0, 3, |_| 0).unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(labels(&graph, &path), ["a", "c", "b", "d"]);
+        assert_eq!(graph.astar(0, 4, |_| 0), None);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let mut graph = Graph::directed();
+        graph.add_edge("shirt", "tie", 1);
+        graph.add_edge("tie", "jacket", 1);
+        graph.add_edge("pants", "shoes", 1);
+        graph.add_edge("pants", "jacket", 1);
+        let order = graph.topological_sort().unwrap();
+        let position = |label| order.iter().position(|x| *x == graph.id(label).unwrap());
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("pants") < position("shoes"));
+
+        graph.add_edge("jacket", "shirt", 1);
+        assert_eq!(graph.topological_sort(), None);
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = Graph::parse_pairs("a = (b)\nb = (c, d)\nc = (a)\nd = (e)\ne = (d)\nf = (a)\n")
+            .unwrap();
+        let components: Vec<_> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|x| labels(&graph, x))
+            .collect();
+        assert_eq!(components, [vec!["f"], vec!["a", "b", "c"], vec!["d", "e"]]);
+    }
+}
@@ -0,0 +1,433 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+mod spans;
+
+pub use spans::*;
+
+/// A position in a [`Grid`], counted from the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    #[must_use]
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves the position by the given offsets, returns [`None`] if either coordinate would become negative.
+    #[must_use]
+    pub fn offset(self, d_row: isize, d_col: isize) -> Option<Self> {
+        Some(Self {
+            row: self.row.checked_add_signed(d_row)?,
+            col: self.col.checked_add_signed(d_col)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+/// Offsets of the horizontally and vertically adjacent cells, in reading order.
+#[rustfmt::skip]
+pub const OFFSETS_4: [(isize, isize); 4] = [
+              (-1, 0),
+    ( 0, -1),          ( 0, 1),
+              ( 1, 0),
+];
+
+/// Offsets of all adjacent cells including diagonals, in reading order.
+#[rustfmt::skip]
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    ( 0, -1),          ( 0, 1),
+    ( 1, -1), ( 1, 0), ( 1, 1),
+];
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular 2D grid, stored row by row in a single [`Vec`].
+///
+/// # Display
+/// The grid displays its cells row by row, with a newline after each row.
+/// The alternate form (`{:#}`) separates cells with a space.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Pos};
+/// let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+/// assert_eq!(grid[Pos::new(1, 0)], 'c');
+/// assert_eq!(grid.transpose().to_string(), "ac\nbd\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Creates a grid from cells in row-major order, returns [`None`] if their number does not match the dimensions.
+    #[must_use]
+    pub fn from_cells(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() != rows * cols {
+            return None;
+        }
+        Some(Self { cells, rows, cols })
+    }
+
+    /// Parses a grid from lines of text, converting every character with `f`.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - len;
+
+            if row == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: cols,
+                    found,
+                });
+            }
+            rows += 1;
+        }
+
+        Ok(Self { cells, rows, cols })
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then_some(pos.row * self.cols + pos.col)
+    }
+
+    /// Returns the cell at `pos`, or [`None`] if it is out of bounds.
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `pos` mutably, or [`None`] if it is out of bounds.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `pos` by the given offsets, returns [`None`] if the result is out of bounds.
+    #[must_use]
+    pub fn offset(&self, pos: Pos, d_row: isize, d_col: isize) -> Option<Pos> {
+        pos.offset(d_row, d_col).filter(|x| self.contains(*x))
+    }
+
+    /// Iterates all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Pos::new(row, col)))
+    }
+
+    /// Iterates all cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell in row-major order that matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, x)| predicate(x)).map(|(pos, _)| pos)
+    }
+
+    /// Iterates the in-bounds positions at the given offsets from `pos`.
+    pub fn neighbors<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(d_row, d_col)| self.offset(pos, d_row, d_col))
+    }
+
+    /// Iterates the in-bounds horizontally and vertically adjacent positions.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors(pos, &OFFSETS_4)
+    }
+
+    /// Iterates the in-bounds adjacent positions, including diagonals.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors(pos, &OFFSETS_8)
+    }
+
+    /// Returns the cells of a row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {row} is out of bounds");
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Returns the cells of a row mutably.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.rows, "row {row} is out of bounds");
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Iterates the cells of a column from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "column {col} is out of bounds");
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    /// Iterates all rows from top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, a grid without columns has no cells either way.
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// Iterates the cells on the diagonal that starts at `pos` and runs down and to the right.
+    pub fn diagonal(&self, pos: Pos) -> impl Iterator<Item = &T> {
+        self.ray(pos, 1, 1)
+    }
+
+    /// Iterates the cells on the anti-diagonal that starts at `pos` and runs down and to the left.
+    pub fn anti_diagonal(&self, pos: Pos) -> impl Iterator<Item = &T> {
+        self.ray(pos, 1, -1)
+    }
+
+    /// Iterates the cells from `pos` (inclusive) in a fixed direction until leaving the grid.
+    pub fn ray(&self, pos: Pos, d_row: isize, d_col: isize) -> impl Iterator<Item = &T> {
+        let start = self.contains(pos).then_some(pos);
+        std::iter::successors(start, move |x| self.offset(*x, d_row, d_col)).map(|x| &self[x])
+    }
+
+    /// Creates a grid of the same dimensions by converting every cell with `f`.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| Pos::new(row, col)))
+            .map(&mut f)
+            .collect();
+        Self { cells, rows, cols }
+    }
+
+    /// Mirrors the grid along its main diagonal, i.e. rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.cols, self.rows, |pos| {
+            self[Pos::new(pos.col, pos.row)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.cols, self.rows, |pos| {
+            self[Pos::new(self.rows - 1 - pos.col, pos.row)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.cols, self.rows, |pos| {
+            self[Pos::new(pos.col, self.cols - 1 - pos.row)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for (col, cell) in row.iter().enumerate() {
+                if f.alternate() && col > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row does not have the same length as the first row.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expecting {expected} like the first row"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError, Pos};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_text() {
+        let grid = grid();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[Pos::new(1, 2)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(format!("{grid:#}"), "a b c\nd e f\n");
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = grid();
+        assert_eq!(grid.get(Pos::new(1, 3)), None);
+        assert_eq!(grid.get(Pos::new(2, 0)), None);
+        assert_eq!(grid.offset(Pos::new(0, 0), -1, 0), None);
+        assert_eq!(grid.offset(Pos::new(0, 0), 1, 1), Some(Pos::new(1, 1)));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = grid();
+        let corner: Vec<_> = grid.neighbors8(Pos::new(0, 0)).map(|x| grid[x]).collect();
+        assert_eq!(corner, ['b', 'd', 'e']);
+        let edge: Vec<_> = grid.neighbors4(Pos::new(1, 1)).map(|x| grid[x]).collect();
+        assert_eq!(edge, ['b', 'd', 'f']);
+    }
+
+    #[test]
+    fn views_rows_columns_and_diagonals() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.col(2).collect::<String>(), "cf");
+        assert_eq!(grid.iter_rows().count(), 2);
+        assert_eq!(grid.diagonal(Pos::new(0, 0)).collect::<String>(), "ae");
+        assert_eq!(grid.anti_diagonal(Pos::new(0, 2)).collect::<String>(), "ce");
+        assert_eq!(grid.ray(Pos::new(1, 2), 0, -1).collect::<String>(), "fed");
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
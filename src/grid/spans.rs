@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+
+use super::{Grid, Pos, OFFSETS_4};
+
+/// Identifies a [`Span`] within [`Spans`]. Ids are assigned in row-major order of the first cell of each span.
+pub type SpanId = usize;
+
+/// The smallest rectangle that contains a set of positions, both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    fn from_pos(pos: Pos) -> Self {
+        Self { min: pos, max: pos }
+    }
+
+    fn extend(&mut self, pos: Pos) {
+        self.min.row = self.min.row.min(pos.row);
+        self.min.col = self.min.col.min(pos.col);
+        self.max.row = self.max.row.max(pos.row);
+        self.max.col = self.max.col.max(pos.col);
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Pos) -> bool {
+        (self.min.row..=self.max.row).contains(&pos.row)
+            && (self.min.col..=self.max.col).contains(&pos.col)
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.max.row - self.min.row + 1
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.max.col - self.min.col + 1
+    }
+}
+
+/// A labeled group of cells, e.g. the digits of a number or a connected region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub id: SpanId,
+    /// The cells of the span in row-major order.
+    pub cells: Vec<Pos>,
+    pub bounds: Bounds,
+}
+
+impl Span {
+    /// Iterates the values of the span's cells in row-major order.
+    pub fn values<'a, T>(&'a self, grid: &'a Grid<T>) -> impl Iterator<Item = &'a T> {
+        self.cells.iter().map(|pos| &grid[*pos])
+    }
+
+    /// Collects the characters of the span, e.g. to parse a number.
+    #[must_use]
+    pub fn text(&self, grid: &Grid<char>) -> String {
+        self.values(grid).collect()
+    }
+}
+
+/// Spans extracted from a [`Grid`], together with a lookup from positions to the span containing them.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Pos, Spans, OFFSETS_8};
+/// let grid: Grid<char> = "12*12\n.....\n".parse().unwrap();
+/// let numbers = Spans::runs(&grid, |c| c.is_ascii_digit());
+/// assert_eq!(numbers.adjacent_to(Pos::new(0, 2), &OFFSETS_8), [0, 1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spans {
+    spans: Vec<Span>,
+    labels: Grid<Option<SpanId>>,
+}
+
+impl Spans {
+    /// Extracts horizontal runs of cells that match `predicate`, e.g. the digits of numbers.
+    pub fn runs<T>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Self {
+        Self::extract(grid, &[(0, 1)], predicate)
+    }
+
+    /// Extracts regions of cells that match `predicate` and are connected via `offsets`,
+    /// e.g. [`OFFSETS_4`](super::OFFSETS_4) or [`OFFSETS_8`](super::OFFSETS_8).
+    pub fn regions<T>(
+        grid: &Grid<T>,
+        offsets: &[(isize, isize)],
+        predicate: impl Fn(&T) -> bool,
+    ) -> Self {
+        Self::extract(grid, offsets, predicate)
+    }
+
+    /// Extracts regions of equal, horizontally or vertically adjacent cells.
+    pub fn regions_by_value<T: PartialEq>(grid: &Grid<T>) -> Self {
+        let mut spans = Self {
+            spans: vec![],
+            labels: Grid::new(grid.rows(), grid.cols(), None),
+        };
+
+        for pos in grid.positions() {
+            if spans.labels[pos].is_none() {
+                let value = &grid[pos];
+                spans.flood(grid, pos, &OFFSETS_4, |x| x == value);
+            }
+        }
+
+        spans
+    }
+
+    fn extract<T>(
+        grid: &Grid<T>,
+        offsets: &[(isize, isize)],
+        predicate: impl Fn(&T) -> bool,
+    ) -> Self {
+        let mut spans = Self {
+            spans: vec![],
+            labels: Grid::new(grid.rows(), grid.cols(), None),
+        };
+
+        for pos in grid.positions() {
+            if spans.labels[pos].is_none() && predicate(&grid[pos]) {
+                spans.flood(grid, pos, offsets, &predicate);
+            }
+        }
+
+        spans
+    }
+
+    /// Labels every cell reachable from `start` as a new span.
+    fn flood<T>(
+        &mut self,
+        grid: &Grid<T>,
+        start: Pos,
+        offsets: &[(isize, isize)],
+        predicate: impl Fn(&T) -> bool,
+    ) {
+        let id = self.spans.len();
+        let mut cells = vec![start];
+        let mut stack = vec![start];
+        self.labels[start] = Some(id);
+
+        while let Some(pos) = stack.pop() {
+            for n_pos in grid.neighbors(pos, offsets) {
+                if self.labels[n_pos].is_none() && predicate(&grid[n_pos]) {
+                    self.labels[n_pos] = Some(id);
+                    cells.push(n_pos);
+                    stack.push(n_pos);
+                }
+            }
+        }
+
+        cells.sort();
+        let mut bounds = Bounds::from_pos(start);
+        cells.iter().for_each(|pos| bounds.extend(*pos));
+
+        self.spans.push(Span { id, cells, bounds });
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the span with the given id.
+    ///
+    /// # Panics
+    /// Panics if no span has this id.
+    #[must_use]
+    pub fn get(&self, id: SpanId) -> &Span {
+        &self.spans[id]
+    }
+
+    /// Iterates all spans in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &Span> {
+        self.spans.iter()
+    }
+
+    /// Returns the id of the span that contains `pos`, if any.
+    #[must_use]
+    pub fn id_at(&self, pos: Pos) -> Option<SpanId> {
+        self.labels.get(pos).copied().flatten()
+    }
+
+    /// Returns the ids of all spans adjacent to `pos` via `offsets`, without duplicates and in id order.
+    /// Two distinct spans are reported separately, even if they hold the same values.
+    #[must_use]
+    pub fn adjacent_to(&self, pos: Pos, offsets: &[(isize, isize)]) -> Vec<SpanId> {
+        let ids: BTreeSet<SpanId> = self
+            .labels
+            .neighbors(pos, offsets)
+            .filter_map(|n_pos| self.id_at(n_pos))
+            .collect();
+        ids.into_iter().collect()
+    }
+
+    /// Returns the cells outside of a span that are adjacent to it via `offsets`, in row-major order.
+    #[must_use]
+    pub fn border_of(&self, id: SpanId, offsets: &[(isize, isize)]) -> Vec<Pos> {
+        let border: BTreeSet<Pos> = self.spans[id]
+            .cells
+            .iter()
+            .flat_map(|pos| self.labels.neighbors(*pos, offsets))
+            .filter(|n_pos| self.id_at(*n_pos) != Some(id))
+            .collect();
+        border.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Bounds, Spans};
+    use crate::grid::{Grid, Pos, OFFSETS_4, OFFSETS_8};
+
+    fn grid() -> Grid<char> {
+        "467..114\n...*....\n..35..46\n".parse().unwrap()
+    }
+
+    #[test]
+    fn extracts_runs() {
+        let grid = grid();
+        let numbers = Spans::runs(&grid, |c| c.is_ascii_digit());
+        let texts: Vec<String> = numbers.iter().map(|x| x.text(&grid)).collect();
+        assert_eq!(texts, ["467", "114", "35", "46"]);
+        assert_eq!(numbers.id_at(Pos::new(2, 3)), Some(2));
+        assert_eq!(numbers.id_at(Pos::new(1, 3)), None);
+        assert_eq!(
+            numbers.get(1).bounds,
+            Bounds {
+                min: Pos::new(0, 5),
+                max: Pos::new(0, 7)
+            }
+        );
+    }
+
+    #[test]
+    fn finds_adjacent_spans_and_borders() {
+        let grid = grid();
+        let numbers = Spans::runs(&grid, |c| c.is_ascii_digit());
+        assert_eq!(numbers.adjacent_to(Pos::new(1, 3), &OFFSETS_8), [0, 2]);
+        assert_eq!(numbers.adjacent_to(Pos::new(1, 3), &OFFSETS_4), [2]);
+        let border = numbers.border_of(2, &OFFSETS_8);
+        assert_eq!(border.len(), 6);
+        assert!(border.contains(&Pos::new(1, 3)));
+    }
+
+    #[test]
+    fn distinguishes_equal_values() {
+        let grid: Grid<char> = "12*12".parse().unwrap();
+        let numbers = Spans::runs(&grid, |c| c.is_ascii_digit());
+        assert_eq!(numbers.adjacent_to(Pos::new(0, 2), &OFFSETS_8), [0, 1]);
+    }
+
+    #[test]
+    fn extracts_regions() {
+        let grid: Grid<char> = "aab\nabb\nccb\n".parse().unwrap();
+        let regions = Spans::regions_by_value(&grid);
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions.get(1).cells.len(), 4);
+        assert_eq!(regions.get(1).bounds.rows(), 3);
+
+        let hashes: Grid<char> = "#.#\n.#.\n".parse().unwrap();
+        assert_eq!(Spans::regions(&hashes, &OFFSETS_4, |c| *c == '#').len(), 3);
+        assert_eq!(Spans::regions(&hashes, &OFFSETS_8, |c| *c == '#').len(), 1);
+    }
+}
@@ -0,0 +1,151 @@
+use std::any::type_name;
+use std::marker::PhantomData;
+
+/// Primitive integer types that can be scanned by [`ints`] and [`uints`].
+pub trait ScanInt: Copy {
+    const ZERO: Self;
+
+    /// Returns `self * 10 + digit`, or `self * 10 - digit` for negative numbers, or [`None`] on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_scan_int {
+    ($($t:ty),*) => {
+        $(
+            impl ScanInt for $t {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    // NOTE: a digit fits into every integer type.
+                    let digit = digit as Self;
+                    if negative {
+                        shifted.checked_sub(digit)
+                    } else {
+                        shifted.checked_add(digit)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_scan_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An iterator over the integers in a string, as returned by [`ints`] and [`uints`].
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    signed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: ScanInt> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let bytes = self.bytes;
+        let start = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let negative = self.signed && start > 0 && bytes[start - 1] == b'-';
+
+        let mut value = T::ZERO;
+        let mut end = start;
+        while let Some(digit) = bytes.get(end).filter(|x| x.is_ascii_digit()) {
+            value = value.push_digit(digit - b'0', negative).unwrap_or_else(|| {
+                panic!(
+                    "integer at byte {start} does not fit into `{}`",
+                    type_name::<T>()
+                )
+            });
+            end += 1;
+        }
+
+        self.pos = end;
+        Some(value)
+    }
+}
+
+/// Iterates the integers in `input` in one pass without allocating.
+/// A `-` directly before a run of digits makes it negative, every other non-digit separates integers.
+///
+/// ```
+/// # use advent_of_code::ints::ints;
+/// let values: Vec<i32> = ints("x=-3, y=12..-7").collect();
+/// assert_eq!(values, [-3, 12, -7]);
+/// ```
+///
+/// # Panics
+/// The iterator panics if an integer does not fit into `T`, including negative integers for unsigned types.
+pub fn ints<T: ScanInt, S: AsRef<[u8]> + ?Sized>(input: &S) -> Ints<'_, T> {
+    Ints {
+        bytes: input.as_ref(),
+        pos: 0,
+        signed: true,
+        marker: PhantomData,
+    }
+}
+
+/// Iterates the runs of digits in `input` in one pass without allocating. Signs are ignored.
+///
+/// ```
+/// # use advent_of_code::ints::uints;
+/// let values: Vec<u64> = uints("Card 1: 41 48 | 83-86").collect();
+/// assert_eq!(values, [1, 41, 48, 83, 86]);
+/// ```
+///
+/// # Panics
+/// The iterator panics if an integer does not fit into `T`.
+pub fn uints<T: ScanInt, S: AsRef<[u8]> + ?Sized>(input: &S) -> Ints<'_, T> {
+    Ints {
+        signed: false,
+        ..ints(input)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ints, uints};
+
+    #[test]
+    fn scans_integers() {
+        let input = "Time:  7 15\n-30 x-2 --4 5-6 +7";
+        assert_eq!(
+            ints::<i64, _>(input).collect::<Vec<_>>(),
+            [7, 15, -30, -2, -4, 5, -6, 7]
+        );
+        assert_eq!(
+            uints::<u8, _>(input.as_bytes()).collect::<Vec<_>>(),
+            [7, 15, 30, 2, 4, 5, 6, 7]
+        );
+        assert_eq!(ints::<u32, _>("no digits").next(), None);
+    }
+
+    #[test]
+    fn scans_integer_limits() {
+        let input = format!("{} {}", i64::MIN, i64::MAX);
+        assert_eq!(
+            ints::<i64, _>(&input).collect::<Vec<_>>(),
+            [i64::MIN, i64::MAX]
+        );
+        assert_eq!(
+            uints::<u128, _>(&u128::MAX.to_string()).next(),
+            Some(u128::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "integer at byte 4 does not fit into `u8`")]
+    fn panics_on_overflow() {
+        uints::<u8, _>("255 256").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into `u32`")]
+    fn panics_on_negative_unsigned() {
+        ints::<u32, _>("-1").for_each(drop);
+    }
+}
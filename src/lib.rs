@@ -1,4 +1,22 @@
+mod answer;
+pub mod cards;
+pub mod cycle;
 mod day;
+pub mod differential;
+pub mod generators;
+pub mod graph;
+pub mod grid;
+pub mod ints;
+pub mod math;
+pub mod model;
+pub mod parse;
+pub mod patterns;
+pub mod poly;
+pub mod property;
+pub mod ranges;
+pub mod rng;
+pub mod snapshot;
 pub mod template;
 
+pub use answer::*;
 pub use day::*;
@@ -1,9 +1,13 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, generate, read, scaffold, solve};
+use advent_of_code::template::output;
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
+    use advent_of_code::template::commands::generate::DEFAULT_SIZE;
+    use advent_of_code::template::output::ColorChoice;
+    use advent_of_code::template::trace::TraceTarget;
     use advent_of_code::Day;
 
     pub enum AppArguments {
@@ -13,14 +17,23 @@ mod args {
         Read {
             day: Day,
         },
+        Generate {
+            day: Day,
+            seed: Option<u64>,
+            size: usize,
+        },
         Scaffold {
             day: Day,
+            properties: bool,
         },
         Solve {
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            inputs: Option<String>,
+            trace: Option<TraceTarget>,
         },
         All {
             release: bool,
@@ -28,7 +41,20 @@ mod args {
         },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    fn parse_trace_target(args: &mut pico_args::Arguments) -> Option<TraceTarget> {
+        let verbose = args.contains("--verbose");
+        let log = args.contains("--log");
+
+        if log {
+            Some(TraceTarget::LogFile)
+        } else if verbose {
+            Some(TraceTarget::Stderr)
+        } else {
+            None
+        }
+    }
+
+    pub fn parse() -> Result<(AppArguments, ColorChoice), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
@@ -39,17 +65,26 @@ mod args {
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("generate") => AppArguments::Generate {
+                day: args.free_from_str()?,
+                seed: args.opt_value_from_str("--seed")?,
+                size: args.opt_value_from_str("--size")?.unwrap_or(DEFAULT_SIZE),
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                properties: args.contains("--properties"),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                input: args.opt_value_from_str("--input")?,
+                inputs: args.opt_value_from_str("--inputs")?,
+                trace: parse_trace_target(&mut args),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -61,12 +96,27 @@ mod args {
             }
         };
 
+        if let AppArguments::Solve {
+            input: Some(_),
+            inputs: Some(_),
+            ..
+        } = app_args
+        {
+            return Err("`--input` and `--inputs` can not be combined.".into());
+        }
+
+        let color = if args.contains("--no-color") {
+            ColorChoice::Never
+        } else {
+            args.opt_value_from_str("--color")?.unwrap_or_default()
+        };
+
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, color))
     }
 }
 
@@ -76,17 +126,32 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
+        Ok((args, color)) => {
+            output::init(color);
+            match args {
+                AppArguments::All { release, time } => all::handle(release, time),
+                AppArguments::Download { day } => download::handle(day),
+                AppArguments::Generate { day, seed, size } => generate::handle(day, seed, size),
+                AppArguments::Read { day } => read::handle(day),
+                AppArguments::Scaffold { day, properties } => scaffold::handle(day, properties),
+                AppArguments::Solve {
+                    day,
+                    release,
+                    time,
+                    submit,
+                    input,
+                    inputs,
+                    trace,
+                } => solve::handle(
+                    day,
+                    release,
+                    time,
+                    submit,
+                    input.as_deref(),
+                    inputs.as_deref(),
+                    trace,
+                ),
+            }
+        }
     };
 }
@@ -0,0 +1,317 @@
+use std::fmt::Debug;
+use std::ops::{Div, Rem, Sub};
+
+/// Primitive integer types that [`gcd`] and [`lcm`] are implemented for.
+pub trait Integer:
+    Copy + Ord + Debug + Sub<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(x: T) -> T {
+    if x < T::ZERO {
+        T::ZERO - x
+    } else {
+        x
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`, which is never negative.
+/// `gcd(0, 0)` is `0`.
+///
+/// # Panics
+/// Panics on overflow if the result is `T::MIN` of a signed type, e.g. `gcd(i64::MIN, 0)`.
+#[must_use]
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        // `T::MIN % -1` overflows, but the remainder is `0`.
+        (a, b) = (b, a.checked_rem(b).unwrap_or(T::ZERO));
+    }
+    abs(a)
+}
+
+/// Returns the least common multiple of `a` and `b`, or [`None`] if it does not fit into `T`.
+/// Divides before multiplying, so only results that are too large overflow.
+#[must_use]
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    abs(a / gcd(a, b)).checked_mul(abs(b))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the greatest common divisor of `a` and `b`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns `base.pow(exp) % modulus` without intermediate overflow.
+///
+/// # Panics
+/// Panics if `modulus` is `0`.
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must not be zero");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    // NOTE: the result is smaller than the `u64` modulus.
+    result as u64
+}
+
+/// Returns `x` in `0..modulus` such that `a * x % modulus == 1`, or [`None`] if `a` and `modulus` are not coprime.
+#[must_use]
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(i128::from(a), i128::from(modulus));
+    if g != 1 {
+        return None;
+    }
+    // NOTE: the result is smaller than the `u64` modulus.
+    Some(x.rem_euclid(i128::from(modulus)) as u64)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+/// Moduli do not need to be coprime.
+///
+/// Returns `(x, m)` such that every solution is `x + k * m`, with `0 <= x < m` and `m` the least common multiple of all moduli.
+/// Returns [`None`] if the system has no solution or the combined modulus overflows.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), None);
+/// ```
+#[must_use]
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let (mut x, mut m) = (0_i128, 1_i128);
+
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let residue = residue.rem_euclid(modulus);
+
+        // x + m * k ≡ residue (mod modulus)  <=>  (m / g) * k ≡ (residue - x) / g (mod modulus / g)
+        let (g, inv, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = ((diff / g).rem_euclid(step)).checked_mul(inv.rem_euclid(step))? % step;
+        let lcm = m.checked_mul(step)?;
+        x = (x + m.checked_mul(k)?).rem_euclid(lcm);
+        m = lcm;
+    }
+
+    Some((x, m))
+}
+
+/// Counts the integers `x` in `0..=sum` with `x * (sum - x) > threshold`, e.g. the ways to win a boat race.
+///
+/// Solves the quadratic with an exact integer square root, so roots at perfect squares are never miscounted.
+///
+/// ```
+/// # use advent_of_code::math::count_products_above;
+/// // 11, 12, ..., 19
+/// assert_eq!(count_products_above(30, 200), 9);
+/// ```
+#[must_use]
+pub fn count_products_above(sum: u64, threshold: u64) -> u64 {
+    let product = |x: u64| u128::from(x) * u128::from(sum - x);
+    let (sum_wide, threshold) = (u128::from(sum), u128::from(threshold));
+
+    // x * (sum - x) > threshold  <=>  x^2 - sum * x + threshold < 0, between the roots (sum ± sqrt(sum^2 - 4 * threshold)) / 2.
+    let Some(discriminant) = (sum_wide * sum_wide).checked_sub(4 * threshold) else {
+        return 0;
+    };
+    // NOTE: the lower root is at most `sum / 2`.
+    let mut x = ((sum_wide - discriminant.isqrt()) / 2) as u64;
+
+    // the floored square root can only put `x` slightly below the first integer past the root.
+    while product(x) <= threshold {
+        if x >= sum / 2 {
+            return 0;
+        }
+        x += 1;
+    }
+
+    // the products are symmetric around `sum / 2`.
+    sum - 2 * x + 1
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_products_above, crt, extended_gcd, gcd, lcm, mod_inverse, mod_pow};
+    use crate::rng::Rng;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12_u32, 18), 6);
+        assert_eq!(gcd(-12_i64, 18), 6);
+        assert_eq!(gcd(0_u8, 0), 0);
+        assert_eq!(gcd(i64::MIN, -1), 1);
+        assert_eq!(gcd(-1, i8::MIN), 1);
+        assert_eq!(gcd(i32::MIN, 6), 2);
+        assert_eq!(lcm(4_u64, 6), Some(12));
+        assert_eq!(lcm(-4_i32, 6), Some(12));
+        assert_eq!(lcm(0_u64, 6), Some(0));
+        // `a * b` overflows, but the result does not.
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+    }
+
+    #[test]
+    fn gcd_properties() {
+        let mut rng = Rng::new(0x1234_5678_9abc_def1);
+        for _ in 0..1000 {
+            let (a, b) = (rng.below(1 << 20) + 1, rng.below(1 << 20) + 1);
+            let g = gcd(a, b);
+            assert_eq!((a % g, b % g), (0, 0));
+            assert_eq!(gcd(a / g, b / g), 1);
+            assert_eq!(lcm(a, b), Some(a / g * b));
+
+            let (g, x, y) = extended_gcd(a.into(), b.into());
+            assert_eq!(i128::from(a) * x + i128::from(b) * y, g);
+        }
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+
+        let mut rng = Rng::new(0xfeed_beef_dead_cafe);
+        for _ in 0..1000 {
+            let modulus = rng.below(1 << 40) + 2;
+            let a = rng.below(modulus);
+            if let Some(inv) = mod_inverse(a, modulus) {
+                assert_eq!(u128::from(a) * u128::from(inv) % u128::from(modulus), 1);
+            } else {
+                assert_ne!(gcd(a, modulus), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn crt_properties() {
+        let mut rng = Rng::new(0x0bad_5eed_0bad_5eed);
+        for _ in 0..1000 {
+            let congruences: Vec<(i128, i128)> = (0..rng.below(4) + 1)
+                .map(|_| {
+                    let modulus = rng.below(30) + 1;
+                    (rng.below(100).into(), modulus.into())
+                })
+                .collect();
+
+            let expected_modulus = congruences
+                .iter()
+                .try_fold(1, |acc, (_, m)| lcm(acc, *m))
+                .unwrap();
+            let brute_force =
+                (0..expected_modulus).find(|x| congruences.iter().all(|(r, m)| x % m == r % m));
+
+            assert_eq!(
+                crt(congruences.iter().copied()),
+                brute_force.map(|x| (x, expected_modulus))
+            );
+        }
+    }
+
+    fn count_products_above_loop(sum: u64, threshold: u64) -> u64 {
+        (0..=sum)
+            .filter(|x| u128::from(*x) * u128::from(sum - x) > threshold.into())
+            .count() as u64
+    }
+
+    #[test]
+    fn counts_products_above() {
+        assert_eq!(count_products_above(7, 9), 4);
+        assert_eq!(count_products_above(15, 40), 8);
+        assert_eq!(count_products_above(30, 200), 9);
+        assert_eq!(count_products_above(71530, 940_200), 71503);
+        assert_eq!(count_products_above(0, 0), 0);
+        assert_eq!(count_products_above(10, 25), 0);
+        assert_eq!(count_products_above(10, 24), 1);
+        // `sum^2` overflows u64.
+        assert_eq!(count_products_above(u64::MAX, 0), u64::MAX - 1);
+
+        let mut rng = Rng::new(0x5eed_1e55_c0ff_ee00);
+        for _ in 0..2000 {
+            let sum = rng.below(300);
+            let threshold = rng.below(sum * sum / 4 + 10);
+            assert_eq!(
+                count_products_above(sum, threshold),
+                count_products_above_loop(sum, threshold),
+                "sum {sum}, threshold {threshold}"
+            );
+            // perfect squares put the roots exactly on integers.
+            let root = rng.below(sum / 2 + 1);
+            let threshold = root * (sum - root);
+            assert_eq!(
+                count_products_above(sum, threshold),
+                count_products_above_loop(sum, threshold),
+                "sum {sum}, threshold {threshold}"
+            );
+        }
+    }
+}
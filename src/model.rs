@@ -0,0 +1,151 @@
+use std::fmt::{Debug, Display};
+
+/// A typed model of a whole puzzle input, which can be printed back in the input format.
+///
+/// Rendering does not have to reproduce the input byte for byte, e.g. when the model does not keep the
+/// order of unordered items, but parsing the rendered input has to return an equal model.
+pub trait InputModel: Sized {
+    type Error: Display;
+
+    fn parse(input: &str) -> Result<Self, Self::Error>;
+
+    /// Renders the model as an input, with every line terminated by a newline.
+    fn render(&self) -> String;
+}
+
+fn parse_or_panic<T: InputModel>(input: &str, what: &str) -> T {
+    T::parse(input).unwrap_or_else(|err| panic!("could not parse the {what}: {err}\n{input}"))
+}
+
+/// Returns a message that points out the first line where `left` and `right` differ, if they do.
+fn first_difference(left: &str, right: &str) -> Option<String> {
+    let (mut left_lines, mut right_lines) = (left.lines(), right.lines());
+    for line in 1.. {
+        match (left_lines.next(), right_lines.next()) {
+            (None, None) => return None,
+            (a, b) if a == b => continue,
+            (a, b) => {
+                return Some(format!(
+                    "line {line} differs:\n  input:    {}\n  rendered: {}",
+                    a.unwrap_or("<missing>"),
+                    b.unwrap_or("<missing>")
+                ))
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Parses `input`, renders the model and parses the rendered input again, and returns the model.
+///
+/// # Panics
+/// Panics if either input does not parse, if the models differ, or if rendering the second model
+/// does not return the same input as rendering the first.
+pub fn assert_round_trip<T: InputModel + PartialEq + Debug>(input: &str) -> T {
+    let model: T = parse_or_panic(input, "input");
+    let rendered = model.render();
+    let reparsed: T = parse_or_panic(&rendered, "rendered input");
+
+    assert!(
+        model == reparsed,
+        "the rendered input parses differently, {}\nparsed:   {model:?}\nreparsed: {reparsed:?}",
+        first_difference(input, &rendered).unwrap_or_else(|| "with the same text".to_string())
+    );
+    if let Some(difference) = first_difference(&rendered, &reparsed.render()) {
+        panic!("rendering is not stable, {difference}");
+    }
+    model
+}
+
+/// Like [`assert_round_trip`], and also checks that the rendered input is exactly `input`.
+///
+/// # Panics
+/// Panics if [`assert_round_trip`] panics, or if the rendered input differs from `input`.
+pub fn assert_exact_round_trip<T: InputModel + PartialEq + Debug>(input: &str) -> T {
+    let model: T = assert_round_trip(input);
+    let rendered = model.render();
+    if let Some(difference) = first_difference(input, &rendered) {
+        panic!("the rendered input differs, {difference}");
+    }
+    assert_eq!(input, rendered, "the rendered input differs in whitespace");
+    model
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{assert_exact_round_trip, assert_round_trip, InputModel};
+    use std::num::ParseIntError;
+
+    /// Lines of numbers, which are rendered separated by single spaces.
+    #[derive(Debug, PartialEq)]
+    struct Rows(Vec<Vec<u32>>);
+
+    impl InputModel for Rows {
+        type Error = ParseIntError;
+
+        fn parse(input: &str) -> Result<Self, Self::Error> {
+            input
+                .lines()
+                .map(|line| line.split_whitespace().map(str::parse).collect())
+                .collect::<Result<_, _>>()
+                .map(Rows)
+        }
+
+        fn render(&self) -> String {
+            self.0
+                .iter()
+                .map(|row| {
+                    let row: Vec<String> = row.iter().map(u32::to_string).collect();
+                    row.join(" ") + "\n"
+                })
+                .collect()
+        }
+    }
+
+    /// Drops the last number of every line when rendering.
+    #[derive(Debug, PartialEq)]
+    struct Lossy(Vec<Vec<u32>>);
+
+    impl InputModel for Lossy {
+        type Error = ParseIntError;
+
+        fn parse(input: &str) -> Result<Self, Self::Error> {
+            Rows::parse(input).map(|x| Lossy(x.0))
+        }
+
+        fn render(&self) -> String {
+            let rows = self
+                .0
+                .iter()
+                .map(|x| x[..x.len().saturating_sub(1)].to_vec());
+            Rows(rows.collect()).render()
+        }
+    }
+
+    #[test]
+    fn round_trips_models() {
+        let model: Rows = assert_exact_round_trip("1 2 3\n4\n");
+        assert_eq!(model, Rows(vec![vec![1, 2, 3], vec![4]]));
+        assert_round_trip::<Rows>("1  2\n 3\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1 differs:\n  input:    1  2\n  rendered: 1 2")]
+    fn reports_different_text() {
+        assert_exact_round_trip::<Rows>("1  2\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "the rendered input parses differently, line 1 differs")]
+    fn reports_different_models() {
+        assert_round_trip::<Lossy>("1\n2 3\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "could not parse the input: invalid digit found in string")]
+    fn reports_parse_errors() {
+        assert_round_trip::<Rows>("1 x\n");
+    }
+}
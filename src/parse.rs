@@ -0,0 +1,436 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A cursor over puzzle input with small combinators that report the line and column of failures.
+///
+/// Token parsers ([`Input::tag`], [`Input::uint`], [`Input::int`], [`Input::word`], [`Input::one_of`])
+/// skip leading spaces and tabs, but never newlines. Fixed-width parsers ([`Input::take`], [`Input::fields`]) skip nothing.
+///
+/// ```
+/// # use advent_of_code::parse::{parse_all, Input};
+/// let cards = parse_all("Card 1: 41 48 | 83 86\nCard 2: 13 | 61\n", |input| {
+///     input.lines(|line| {
+///         let id: u32 = line.labeled("Card", Input::uint)?;
+///         line.tag(":")?;
+///         let winning: Vec<u32> = line.many(Input::uint);
+///         line.tag("|")?;
+///         Ok((id, winning, line.many(Input::uint::<u32>)))
+///     })
+/// });
+/// assert_eq!(cards.unwrap()[1], (2, vec![13], vec![61]));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    src: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+/// Runs `parser` on all of `src`, which may only be followed by whitespace.
+pub fn parse_all<'a, T>(
+    src: &'a str,
+    parser: impl FnOnce(&mut Input<'a>) -> ParseResult<T>,
+) -> ParseResult<T> {
+    let mut input = Input::new(src);
+    let value = parser(&mut input)?;
+    input.pos += input.rest().len() - input.rest().trim_start().len();
+    input.end()?;
+    Ok(value)
+}
+
+impl<'a> Input<'a> {
+    #[must_use]
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            end: src.len(),
+        }
+    }
+
+    /// The input that has not been consumed yet.
+    #[must_use]
+    pub fn rest(&self) -> &'a str {
+        &self.src[self.pos..self.end]
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.end
+    }
+
+    /// Creates an error at the current position.
+    #[must_use]
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let before = &self.src[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+        let found = self.rest().lines().next().unwrap_or_default();
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            col: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found: match found {
+                "" if self.is_empty() => "end of input".into(),
+                "" => "end of line".into(),
+                _ => format!("`{}`", found.chars().take(20).collect::<String>()),
+            },
+        }
+    }
+
+    /// An input limited to the next `len` bytes, which keeps reporting positions relative to the whole source.
+    fn sub(&self, len: usize) -> Self {
+        Self {
+            end: self.pos + len,
+            ..*self
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.rest()[..len];
+        self.pos += len;
+        taken
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let len = self
+            .rest()
+            .find(|c| !predicate(c))
+            .unwrap_or(self.rest().len());
+        self.advance(len)
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Skips spaces and tabs.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Succeeds if only spaces are left.
+    pub fn end(&mut self) -> ParseResult<()> {
+        self.finish("end of input")
+    }
+
+    fn finish(&mut self, expected: &str) -> ParseResult<()> {
+        self.skip_spaces();
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    /// Consumes `tag`.
+    pub fn tag(&mut self, tag: &str) -> ParseResult<()> {
+        self.skip_spaces();
+        if self.rest().starts_with(tag) {
+            self.advance(tag.len());
+            Ok(())
+        } else {
+            Err(self.error(format!("`{tag}`")))
+        }
+    }
+
+    /// Consumes the first of `options` that the input starts with.
+    pub fn one_of(&mut self, options: &[&str]) -> ParseResult<&'a str> {
+        self.skip_spaces();
+        match options.iter().find(|x| self.rest().starts_with(**x)) {
+            Some(option) => Ok(self.advance(option.len())),
+            None => Err(self.error(format!("one of {options:?}"))),
+        }
+    }
+
+    /// Consumes a non-empty run of alphanumeric characters and underscores.
+    pub fn word(&mut self) -> ParseResult<&'a str> {
+        self.skip_spaces();
+        match self.take_while(|c| c.is_alphanumeric() || c == '_') {
+            "" => Err(self.error("a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// Consumes an unsigned integer.
+    pub fn uint<T: FromStr>(&mut self) -> ParseResult<T> {
+        self.skip_spaces();
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        self.number(len)
+    }
+
+    /// Consumes an integer with an optional `+` or `-` sign.
+    pub fn int<T: FromStr>(&mut self) -> ParseResult<T> {
+        self.skip_spaces();
+        let sign = usize::from(self.rest().starts_with(['+', '-']));
+        let len = self.rest()[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(self.rest().len(), |x| x + sign);
+        self.number(len)
+    }
+
+    fn number<T: FromStr>(&mut self, len: usize) -> ParseResult<T> {
+        let digits = &self.rest()[..len];
+        if !digits.ends_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error("an integer"));
+        }
+        match digits.parse() {
+            Ok(value) => {
+                self.advance(len);
+                Ok(value)
+            }
+            Err(_) => Err(self.error(format!(
+                "an integer that fits into `{}`",
+                std::any::type_name::<T>()
+            ))),
+        }
+    }
+
+    /// Consumes the next `n` characters of the current line.
+    pub fn take(&mut self, n: usize) -> ParseResult<&'a str> {
+        let line = self.rest().lines().next().unwrap_or_default();
+        match line.char_indices().nth(n) {
+            Some((len, _)) => Ok(self.advance(len)),
+            None if line.chars().count() == n => Ok(self.advance(line.len())),
+            None => Err(self.error(format!("{n} characters"))),
+        }
+    }
+
+    /// Consumes consecutive fixed-width fields of the current line.
+    pub fn fields(&mut self, widths: &[usize]) -> ParseResult<Vec<&'a str>> {
+        widths.iter().map(|n| self.take(*n)).collect()
+    }
+
+    /// Consumes the rest of the current line and its line break, which may be `\r\n` like in [`str::lines`].
+    pub fn line(&mut self) -> &'a str {
+        let line = self.take_while(|c| c != '\n');
+        if self.rest().starts_with('\n') {
+            self.advance(1);
+        }
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    /* ---------------------------------------------------------------------- */
+
+    /// Consumes `label` followed by `item`, e.g. `Game 12`.
+    pub fn labeled<T>(
+        &mut self,
+        label: &str,
+        item: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.tag(label)?;
+        item(self)
+    }
+
+    /// Consumes `key`, `separator` and `value`, e.g. `seeds: 79 14`.
+    pub fn key_value<K, V>(
+        &mut self,
+        separator: &str,
+        key: impl FnOnce(&mut Self) -> ParseResult<K>,
+        value: impl FnOnce(&mut Self) -> ParseResult<V>,
+    ) -> ParseResult<(K, V)> {
+        let key = key(self)?;
+        self.tag(separator)?;
+        Ok((key, value(self)?))
+    }
+
+    /// Consumes `item` as often as it succeeds. A failed attempt consumes nothing.
+    pub fn many<T>(&mut self, mut item: impl FnMut(&mut Self) -> ParseResult<T>) -> Vec<T> {
+        let mut items = vec![];
+        loop {
+            let start = self.pos;
+            match item(self) {
+                // stop on items that consume nothing, which would otherwise repeat forever.
+                Ok(value) if self.pos == start => {
+                    items.push(value);
+                    return items;
+                }
+                Ok(value) => items.push(value),
+                Err(_) => {
+                    self.pos = start;
+                    return items;
+                }
+            }
+        }
+    }
+
+    /// Consumes one or more `item`s separated by `separator`.
+    pub fn sep_by<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.tag(separator).is_ok() {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Consumes the rest of the input line by line. `item` has to consume each line completely.
+    pub fn lines<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let mut items = vec![];
+        while !self.is_empty() {
+            let len = self.rest().find('\n').unwrap_or(self.rest().len());
+            let mut line = self.sub(len - usize::from(self.rest()[..len].ends_with('\r')));
+            items.push(item(&mut line)?);
+            line.finish("end of line")?;
+            self.advance(len);
+            if self.rest().starts_with('\n') {
+                self.advance(1);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Consumes the rest of the input as blocks separated by blank lines. `item` has to consume each block completely.
+    pub fn blocks<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let mut items = vec![];
+        self.take_while(|c| c == '\n' || c == '\r');
+        while !self.is_empty() {
+            let len = self.block_len();
+            let mut block = self.sub(len);
+            items.push(item(&mut block)?);
+            block.take_while(char::is_whitespace);
+            block.finish("end of block")?;
+            self.advance(len);
+            self.take_while(|c| c == '\n' || c == '\r');
+        }
+        Ok(items)
+    }
+
+    /// The length of the input up to the first blank line, which may end with `\r\n`.
+    fn block_len(&self) -> usize {
+        let rest = self.rest();
+        rest.match_indices('\n')
+            .map(|(i, _)| i)
+            .find(|&i| rest[i + 1..].starts_with('\n') || rest[i + 1..].starts_with("\r\n"))
+            .unwrap_or(rest.len())
+    }
+}
+
+/// An error which can be returned when parsing [`Input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line number, starting at `1`.
+    pub line: usize,
+    /// The column in characters, starting at `1`.
+    pub col: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line, self.col, self.expected, self.found
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_all, Input, ParseError};
+
+    #[test]
+    fn parses_integers() {
+        let mut input = Input::new("  12 -3 +4 300");
+        assert_eq!(input.uint::<u32>(), Ok(12));
+        assert!(input.uint::<u32>().is_err());
+        assert_eq!(input.int::<i64>(), Ok(-3));
+        assert_eq!(input.int::<i8>(), Ok(4));
+        assert_eq!(
+            input.uint::<u8>().unwrap_err().expected,
+            "an integer that fits into `u8`"
+        );
+        assert_eq!(input.uint::<u16>(), Ok(300));
+        assert!(input.end().is_ok());
+    }
+
+    #[test]
+    fn parses_lists_and_fields() {
+        let mut input = Input::new("Game 7: 3 blue, 4 red; 1 red");
+        assert_eq!(input.labeled("Game", Input::uint), Ok(7_u32));
+        input.tag(":").unwrap();
+        let sets = input.sep_by(";", |input| {
+            input.sep_by(",", |input| Ok((input.uint::<u32>()?, input.word()?)))
+        });
+        assert_eq!(
+            sets.unwrap(),
+            [vec![(3, "blue"), (4, "red")], vec![(1, "red")]]
+        );
+
+        let mut input = Input::new("AAA = (BBB, CCC)");
+        assert_eq!(input.fields(&[3, 4, 3]).unwrap(), ["AAA", " = (", "BBB"]);
+        assert!(input.take(7).is_err());
+        assert_eq!(input.take(6), Ok(", CCC)"));
+    }
+
+    #[test]
+    fn parses_blocks_of_key_value_lines() {
+        let src = "seeds: 1 2\n\nx map:\n1 2\n3 4\n\ny map:\n5 6\n";
+        let (seeds, maps) = parse_all(src, |input| {
+            let (_, seeds) =
+                input.key_value(":", Input::word, |x| Ok(x.many(Input::uint::<u32>)))?;
+            let maps = input.blocks(|block| {
+                block.line();
+                block.lines(|line| Ok((line.uint::<u32>()?, line.uint::<u32>()?)))
+            })?;
+            Ok((seeds, maps))
+        })
+        .unwrap();
+        assert_eq!(seeds, [1, 2]);
+        assert_eq!(maps, [vec![(1, 2), (3, 4)], vec![(5, 6)]]);
+    }
+
+    #[test]
+    fn parses_crlf_line_endings() {
+        let src = "seeds: 1 2\r\n\r\nx map:\r\n1 2\r\n3 4\r\n\r\ny map:\r\n5 6\r\n";
+        let maps = parse_all(src, |input| {
+            assert_eq!(input.line(), "seeds: 1 2");
+            input.blocks(|block| {
+                assert!(block.line().ends_with(':'));
+                block.lines(|line| Ok((line.uint::<u32>()?, line.uint::<u32>()?)))
+            })
+        });
+        assert_eq!(maps.unwrap(), [vec![(1, 2), (3, 4)], vec![(5, 6)]]);
+    }
+
+    #[test]
+    fn reports_error_locations() {
+        let error = parse_all("1 2\n3 x\n", |input| {
+            input.lines(|line| Ok(line.many(Input::uint::<u32>)))
+        })
+        .unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                col: 3,
+                expected: "end of line".into(),
+                found: "`x`".into()
+            }
+        );
+        assert_eq!(error.to_string(), "2:3: expected end of line, found `x`");
+
+        let error = parse_all("a:\n\nb", |input| input.blocks(|x| x.tag("a:"))).unwrap_err();
+        assert_eq!((error.line, error.col), (3, 1));
+        assert_eq!(Input::new("").word().unwrap_err().found, "end of input");
+    }
+}
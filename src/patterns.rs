@@ -0,0 +1,187 @@
+use aho_corasick::{AhoCorasick, Anchored, BuildError, Input, MatchKind, StartKind};
+
+/// A match of a [`PatternMap`] pattern at `start..end` of a haystack.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PatternMatch<'a, V> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a V,
+}
+
+// NOTE: implemented by hand, deriving would require `V: Copy`.
+impl<V> Clone for PatternMatch<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for PatternMatch<'_, V> {}
+
+/// Maps several patterns to values with a single Aho-Corasick automaton, built once and reused for every haystack.
+///
+/// Matches may overlap: in `oneight`, `one` is the first match and `eight` is the last.
+/// If several patterns start at the same position, the longest one matches.
+///
+/// ```
+/// # use advent_of_code::patterns::PatternMap;
+/// let digits = PatternMap::new([("1", 1), ("one", 1), ("8", 8), ("eight", 8)]).unwrap();
+/// assert_eq!(digits.first("xoneight").map(|x| *x.value), Some(1));
+/// assert_eq!(digits.last("xoneight").map(|x| *x.value), Some(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PatternMap<V> {
+    automaton: AhoCorasick,
+    values: Vec<V>,
+}
+
+impl<V> PatternMap<V> {
+    pub fn new<P: AsRef<str>>(
+        entries: impl IntoIterator<Item = (P, V)>,
+    ) -> Result<Self, BuildError> {
+        let (patterns, values): (Vec<P>, Vec<V>) = entries.into_iter().unzip();
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            // anchored searches probe single positions when searching from the end.
+            .start_kind(StartKind::Both)
+            .build(patterns.iter().map(AsRef::as_ref))?;
+        Ok(Self { automaton, values })
+    }
+
+    fn to_match(&self, mat: aho_corasick::Match) -> PatternMatch<'_, V> {
+        PatternMatch {
+            start: mat.start(),
+            end: mat.end(),
+            value: &self.values[mat.pattern()],
+        }
+    }
+
+    /// Returns the match that starts first.
+    #[must_use]
+    pub fn first(&self, haystack: &str) -> Option<PatternMatch<'_, V>> {
+        self.automaton.find(haystack).map(|x| self.to_match(x))
+    }
+
+    /// Returns the match that starts last, searching backwards from the end of `haystack`.
+    ///
+    /// Every step only tries the patterns that start at one position, so matches near the end are found quickly.
+    #[must_use]
+    pub fn last(&self, haystack: &str) -> Option<PatternMatch<'_, V>> {
+        (0..haystack.len())
+            .rev()
+            .filter(|start| haystack.is_char_boundary(*start))
+            .find_map(|start| {
+                let input = Input::new(haystack)
+                    .span(start..haystack.len())
+                    .anchored(Anchored::Yes);
+                self.automaton.find(input)
+            })
+            .map(|x| self.to_match(x))
+    }
+
+    /// Returns the first and the last match, which are the same if there is only one.
+    #[must_use]
+    pub fn first_and_last(
+        &self,
+        haystack: &str,
+    ) -> Option<(PatternMatch<'_, V>, PatternMatch<'_, V>)> {
+        let first = self.first(haystack)?;
+        // the last match cannot start before the first one.
+        let last = self.last(&haystack[first.start..])?;
+        Some((
+            first,
+            PatternMatch {
+                start: last.start + first.start,
+                end: last.end + first.start,
+                ..last
+            },
+        ))
+    }
+
+    /// The number of patterns.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PatternMap, PatternMatch};
+
+    fn digits() -> PatternMap<u32> {
+        let words = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let digits = (0..10).map(|x| (x.to_string(), x));
+        let words = words.iter().zip(0..).map(|(x, v)| (x.to_string(), v));
+        PatternMap::new(digits.chain(words)).unwrap()
+    }
+
+    #[test]
+    fn finds_overlapping_first_and_last() {
+        let digits = digits();
+        assert_eq!(digits.len(), 20);
+
+        let (first, last) = digits.first_and_last("xtwoneightx").unwrap();
+        assert_eq!(
+            first,
+            PatternMatch {
+                start: 1,
+                end: 4,
+                value: &2
+            }
+        );
+        assert_eq!(
+            last,
+            PatternMatch {
+                start: 5,
+                end: 10,
+                value: &8
+            }
+        );
+        let (first, last) = digits.first_and_last("7pqrst").unwrap();
+        assert_eq!(first, last);
+        assert_eq!(digits.first_and_last("abc"), None);
+    }
+
+    #[test]
+    fn prefers_longest_pattern_at_a_position() {
+        let map = PatternMap::new([("ab", 'x'), ("abcd", 'y'), ("bc", 'z')]).unwrap();
+        assert_eq!(map.first("abcd").map(|x| *x.value), Some('y'));
+        assert_eq!(map.last("abcd").map(|x| *x.value), Some('z'));
+        // multi-byte characters are skipped when searching backwards.
+        assert_eq!(map.last("ab€").map(|x| x.start), Some(0));
+    }
+
+    #[test]
+    fn last_matches_scanning_all_overlapping_matches() {
+        let digits = digits();
+        for haystack in [
+            "oneight",
+            "eightwothree",
+            "5fivex",
+            "nineeight7seven",
+            "sevenine",
+        ] {
+            let expected = (0..haystack.len())
+                .filter_map(|start| {
+                    digits
+                        .first(&haystack[start..])
+                        .map(|x| (start + x.start, *x.value))
+                })
+                .max();
+            assert_eq!(
+                digits.last(haystack).map(|x| (x.start, *x.value)),
+                expected,
+                "{haystack}"
+            );
+        }
+    }
+}
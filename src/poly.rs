@@ -0,0 +1,279 @@
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+/// Signed integer types that sequences can be extrapolated over.
+pub trait Sample: Copy + Eq + Debug + From<i64> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_sample {
+    ($($t:ty),*) => {
+        $(
+            impl Sample for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_sample!(i64, i128);
+
+/// An error which can be returned when extrapolating a sequence.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExtrapolationError {
+    /// There are no values to extrapolate from.
+    Empty,
+    /// An intermediate value or the result does not fit into the value type.
+    Overflow,
+}
+
+impl Error for ExtrapolationError {}
+
+impl Display for ExtrapolationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtrapolationError::Empty => write!(f, "cannot extrapolate an empty sequence."),
+            ExtrapolationError::Overflow => write!(f, "the extrapolation overflowed."),
+        }
+    }
+}
+
+/// Returns the generalized binomial coefficient `a * (a - 1) * ... * (a - k + 1) / k!`, which is defined for negative `a`.
+fn binomial<T: Sample>(a: T, k: usize) -> Result<T, ExtrapolationError> {
+    (0..k).try_fold(T::ONE, |acc, i| {
+        let i = T::from(i as i64);
+        // NOTE: the product of `i + 1` consecutive integers is divisible by `(i + 1)!`, so the division is exact.
+        acc.checked_mul(a.checked_sub(i).ok_or(ExtrapolationError::Overflow)?)
+            .and_then(|x| x.checked_div(i.checked_add(T::ONE)?))
+            .ok_or(ExtrapolationError::Overflow)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The table of finite differences of a sequence, where every row holds the differences of neighboring values of the row above.
+///
+/// The table stops at the first row that is all zeros, or at a row with a single value.
+///
+/// ```
+/// # use advent_of_code::poly::Differences;
+/// let table = Differences::new(&[1_i64, 3, 6, 10, 15, 21]).unwrap();
+/// assert_eq!(table.rows()[1], [2, 3, 4, 5, 6]);
+/// assert_eq!(table.degree(), Some(2));
+/// assert_eq!(table.eval(6), Ok(28));
+/// assert_eq!(table.eval(-1), Ok(0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Differences<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: Sample> Differences<T> {
+    pub fn new(values: &[T]) -> Result<Self, ExtrapolationError> {
+        if values.is_empty() {
+            return Err(ExtrapolationError::Empty);
+        }
+
+        let mut rows = vec![values.to_vec()];
+        loop {
+            let last = rows.last().unwrap();
+            if last.len() == 1 || last.iter().all(|x| *x == T::ZERO) {
+                return Ok(Self { rows });
+            }
+            let next = last
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]).ok_or(ExtrapolationError::Overflow))
+                .collect::<Result<_, _>>()?;
+            rows.push(next);
+        }
+    }
+
+    /// The rows of the table, starting with the values themselves.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    /// Returns the degree of the polynomial that generates the values, or [`None`] if there are too few values to tell.
+    /// The degree of the zero polynomial is `0`.
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        let last = self.rows.last().unwrap();
+        if last.iter().all(|x| *x == T::ZERO) {
+            Some(self.rows.len().saturating_sub(2))
+        } else {
+            None
+        }
+    }
+
+    /// Evaluates the interpolating polynomial at index `x` with Newton's forward difference formula.
+    /// Indices of the values are `0..len`, negative indices extrapolate backwards.
+    pub fn eval(&self, x: i64) -> Result<T, ExtrapolationError> {
+        self.rows
+            .iter()
+            .enumerate()
+            .try_fold(T::ZERO, |acc, (j, row)| {
+                binomial(T::from(x), j)?
+                    .checked_mul(row[0])
+                    .and_then(|term| acc.checked_add(term))
+                    .ok_or(ExtrapolationError::Overflow)
+            })
+    }
+}
+
+/// Evaluates the polynomial of lowest degree through `values` at index `x`, using Newton's forward difference formula.
+pub fn newton<T: Sample>(values: &[T], x: i64) -> Result<T, ExtrapolationError> {
+    Differences::new(values)?.eval(x)
+}
+
+/// Evaluates the polynomial of degree `values.len() - 1` through `values` at index `x`, using the Lagrange form.
+///
+/// For the integer nodes `0..n`, every basis polynomial at an integer `x` is the integer
+/// `(-1)^(n - 1 - i) * C(x, i) * C(x - i - 1, n - 1 - i)`, so no fractions are needed.
+pub fn lagrange<T: Sample>(values: &[T], x: i64) -> Result<T, ExtrapolationError> {
+    if values.is_empty() {
+        return Err(ExtrapolationError::Empty);
+    }
+    if let Some(value) = usize::try_from(x).ok().and_then(|i| values.get(i)) {
+        return Ok(*value);
+    }
+
+    let n = values.len();
+    let x = T::from(x);
+
+    values
+        .iter()
+        .enumerate()
+        .try_fold(T::ZERO, |acc, (i, value)| {
+            let rest = x
+                .checked_sub(T::from(i as i64 + 1))
+                .ok_or(ExtrapolationError::Overflow)?;
+            let basis = binomial(x, i)?
+                .checked_mul(binomial(rest, n - 1 - i)?)
+                .ok_or(ExtrapolationError::Overflow)?;
+            let term = basis
+                .checked_mul(*value)
+                .ok_or(ExtrapolationError::Overflow)?;
+            if (n - i) % 2 == 1 {
+                acc.checked_add(term)
+            } else {
+                acc.checked_sub(term)
+            }
+            .ok_or(ExtrapolationError::Overflow)
+        })
+}
+
+/// Returns the value `k` steps after the last value.
+pub fn forward<T: Sample>(values: &[T], k: u32) -> Result<T, ExtrapolationError> {
+    newton(values, values.len() as i64 - 1 + i64::from(k))
+}
+
+/// Returns the value `k` steps before the first value.
+pub fn backward<T: Sample>(values: &[T], k: u32) -> Result<T, ExtrapolationError> {
+    newton(values, -i64::from(k))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{backward, forward, lagrange, newton, Differences, ExtrapolationError};
+    use crate::rng::Rng;
+
+    fn eval_poly(coefficients: &[i128], x: i128) -> i128 {
+        coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
+    }
+
+    #[test]
+    fn extrapolates_day_09_example() {
+        let values = [10_i64, 13, 16, 21, 30, 45];
+        assert_eq!(forward(&values, 1), Ok(68));
+        assert_eq!(backward(&values, 1), Ok(5));
+        assert_eq!(forward(&values, 3), Ok(lagrange(&values, 8).unwrap()));
+        assert_eq!(newton::<i64>(&[], 0), Err(ExtrapolationError::Empty));
+        assert_eq!(Differences::new(&[7_i64]).unwrap().degree(), None);
+        assert_eq!(Differences::new(&[7_i64, 7]).unwrap().degree(), Some(0));
+    }
+
+    #[test]
+    fn detects_overflow() {
+        assert_eq!(
+            forward(&[0, i64::MAX], 1),
+            Err(ExtrapolationError::Overflow)
+        );
+        assert_eq!(
+            Differences::new(&[i64::MIN, i64::MAX]),
+            Err(ExtrapolationError::Overflow)
+        );
+        // the same values fit when widened.
+        assert_eq!(
+            forward(&[0, i128::from(i64::MAX)], 1),
+            Ok(2 * i128::from(i64::MAX))
+        );
+    }
+
+    #[test]
+    fn methods_match_polynomials() {
+        let mut rng = Rng::new(0xdead_beef_1234_5678);
+        for _ in 0..500 {
+            let degree = rng.below(6) as usize;
+            let coefficients: Vec<i128> = (0..=degree)
+                .map(|_| rng.range_i64(-20..21).into())
+                .collect();
+            let len = degree + 1 + rng.below(4) as usize;
+            let values: Vec<i64> = (0..len as i128)
+                .map(|x| eval_poly(&coefficients, x) as i64)
+                .collect();
+
+            let table = Differences::new(&values).unwrap();
+            assert!(table.degree().is_none_or(|x| x <= degree));
+
+            for x in -10..(len as i64 + 10) {
+                let expected = eval_poly(&coefficients, x.into()) as i64;
+                assert_eq!(newton(&values, x), Ok(expected), "{values:?} at {x}");
+                assert_eq!(lagrange(&values, x), Ok(expected), "{values:?} at {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn methods_match_on_arbitrary_sequences() {
+        let mut rng = Rng::new(0x0123_4567_89ab_cdef);
+        for _ in 0..500 {
+            let len = rng.below(12) as usize + 1;
+            let values: Vec<i128> = (0..len)
+                .map(|_| rng.range_i64(-1000..1001).into())
+                .collect();
+            for x in -5..(len as i64 + 5) {
+                assert_eq!(
+                    newton(&values, x),
+                    lagrange(&values, x),
+                    "{values:?} at {x}"
+                );
+            }
+        }
+    }
+}
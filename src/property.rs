@@ -0,0 +1,399 @@
+//! Lightweight property-based testing: check an invariant on many random values, and shrink the first value it
+//! fails for to a small counterexample.
+//!
+//! ```
+//! # use advent_of_code::property::{check, ints, vecs};
+//! check(vecs(ints(0_u32..100), 0..20), |values| {
+//!     let mut sorted = values.clone();
+//!     sorted.sort_unstable();
+//!     sorted.len() == values.len() && sorted.windows(2).all(|w| w[0] <= w[1])
+//! });
+//! ```
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::rng::Rng;
+
+/// Shrinking stops after this many steps, in case a strategy shrinks in circles.
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// Generates random values and proposes smaller candidates for a failing value.
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Returns values that are smaller than `value`, the most aggressive candidates first.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// How many cases [`check_with`] runs, and the seed they are generated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cases: 256,
+            seed: 0,
+        }
+    }
+}
+
+/// A value a property failed for, after shrinking.
+#[derive(Debug, Clone)]
+pub struct Counterexample<T> {
+    pub value: T,
+    /// The number of cases that passed before the first failure.
+    pub passed: usize,
+    /// The number of times the value was shrunk.
+    pub shrinks: usize,
+    /// The panic message, if the property panicked instead of returning `false`.
+    pub panic: Option<String>,
+}
+
+/// Checks `property` on 256 values of `strategy`, see [`check_with`].
+///
+/// # Panics
+/// Panics with a shrunk counterexample if the property does not hold.
+pub fn check<S: Strategy>(strategy: S, property: impl Fn(&S::Value) -> bool) {
+    check_with(&Config::default(), strategy, property);
+}
+
+/// Checks `property` on random values of `strategy`. The property fails if it returns `false` or panics,
+/// so it can use `assert!` to explain what went wrong.
+///
+/// # Panics
+/// Panics with a shrunk counterexample if the property does not hold.
+pub fn check_with<S: Strategy>(config: &Config, strategy: S, property: impl Fn(&S::Value) -> bool) {
+    if let Some(found) = counterexample(config, &strategy, property) {
+        let reason = found.panic.map_or_else(
+            || "returned false".to_string(),
+            |x| format!("panicked: {x}"),
+        );
+        panic!(
+            "property {reason} after {} passing cases (seed {}, shrunk {} times):\n{:#?}",
+            found.passed, config.seed, found.shrinks, found.value
+        );
+    }
+}
+
+/// Returns the shrunk value `property` fails for first, or [`None`] if it holds for all cases.
+pub fn counterexample<S: Strategy>(
+    config: &Config,
+    strategy: &S,
+    property: impl Fn(&S::Value) -> bool,
+) -> Option<Counterexample<S::Value>> {
+    let failure = |value: &S::Value| match panic::catch_unwind(AssertUnwindSafe(|| property(value)))
+    {
+        Ok(true) => None,
+        Ok(false) => Some(None),
+        Err(payload) => Some(Some(panic_message(payload.as_ref()))),
+    };
+
+    let mut rng = Rng::new(config.seed);
+    for passed in 0..config.cases {
+        let mut value = strategy.generate(&mut rng);
+        let Some(mut panic) = failure(&value) else {
+            continue;
+        };
+
+        let mut shrinks = 0;
+        while shrinks < MAX_SHRINK_STEPS {
+            let smaller = strategy
+                .shrink(&value)
+                .into_iter()
+                .find_map(|x| failure(&x).map(|panic| (x, panic)));
+            let Some((smaller, smaller_panic)) = smaller else {
+                break;
+            };
+            (value, panic) = (smaller, smaller_panic);
+            shrinks += 1;
+        }
+
+        return Some(Counterexample {
+            value,
+            passed,
+            shrinks,
+            panic,
+        });
+    }
+    None
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Primitive integer types that [`ints`] can generate.
+pub trait PropInt: Copy + Debug + PartialOrd {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_prop_int {
+    ($($t:ty),*) => {
+        $(
+            impl PropInt for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    // NOTE: only called with values inside of a range of `Self`.
+                    value as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_prop_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Integers in a range, which shrink towards the value closest to zero, see [`ints`].
+#[derive(Debug, Clone)]
+pub struct Ints<T> {
+    range: Range<T>,
+}
+
+/// Generates integers in `range`.
+///
+/// # Panics
+/// Panics if `range` is empty.
+#[must_use]
+pub fn ints<T: PropInt>(range: Range<T>) -> Ints<T> {
+    assert!(range.start < range.end, "range must not be empty");
+    Ints { range }
+}
+
+impl<T: PropInt> Strategy for Ints<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        let (start, end) = (self.range.start.to_i128(), self.range.end.to_i128());
+        // NOTE: the width of a range of a 64-bit type fits into `u64`.
+        T::from_i128(start + i128::from(rng.below((end - start) as u64)))
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let (start, end) = (self.range.start.to_i128(), self.range.end.to_i128());
+        let target = 0.clamp(start, end - 1);
+        let value = value.to_i128();
+
+        // the target first, then halve the distance to `value` until it is a single step.
+        let distance = value - target;
+        (0..127)
+            .map(|i| distance / (1 << i))
+            .take_while(|x| *x != 0)
+            .map(|x| T::from_i128(value - x))
+            .collect()
+    }
+}
+
+/// Vectors of values from another strategy, see [`vecs`].
+#[derive(Debug, Clone)]
+pub struct Vecs<S> {
+    element: S,
+    len: Range<usize>,
+}
+
+/// Generates vectors of `element` values with a length in `len`.
+/// Vectors shrink by removing elements first, then by shrinking single elements.
+///
+/// # Panics
+/// Panics if `len` is empty.
+#[must_use]
+pub fn vecs<S: Strategy>(element: S, len: Range<usize>) -> Vecs<S> {
+    assert!(!len.is_empty(), "length range must not be empty");
+    Vecs { element, len }
+}
+
+/// Returns `items` with chunks of half, a quarter, ... down to single items removed, keeping at least `min_len`.
+fn removals<T: Clone>(items: &[T], min_len: usize) -> Vec<Vec<T>> {
+    let mut result = vec![];
+    let mut chunk = items.len().saturating_sub(min_len);
+    while chunk > 0 {
+        for start in (0..=items.len() - chunk).step_by(chunk) {
+            result.push([&items[..start], &items[start + chunk..]].concat());
+        }
+        chunk /= 2;
+    }
+    result
+}
+
+impl<S: Strategy> Strategy for Vecs<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let len = rng.range(self.len.start as u64..self.len.end as u64) as usize;
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let mut result = removals(value, self.len.start);
+        for (i, element) in value.iter().enumerate() {
+            for smaller in self.element.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[i] = smaller;
+                result.push(candidate);
+            }
+        }
+        result
+    }
+}
+
+/// Strings of characters from an alphabet, see [`strings`].
+#[derive(Debug, Clone)]
+pub struct Strings {
+    alphabet: Vec<char>,
+    len: Range<usize>,
+}
+
+/// Generates strings of `alphabet` characters with a length in `len`, e.g. `strings("#.\n", 1..100)`.
+/// Strings shrink by removing characters first, then by replacing them with the first character of `alphabet`.
+///
+/// # Panics
+/// Panics if `alphabet` or `len` is empty.
+#[must_use]
+pub fn strings(alphabet: &str, len: Range<usize>) -> Strings {
+    assert!(!alphabet.is_empty(), "alphabet must not be empty");
+    assert!(!len.is_empty(), "length range must not be empty");
+    Strings {
+        alphabet: alphabet.chars().collect(),
+        len,
+    }
+}
+
+impl Strategy for Strings {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let len = rng.range(self.len.start as u64..self.len.end as u64);
+        (0..len).map(|_| *rng.choose(&self.alphabet)).collect()
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let chars: Vec<char> = value.chars().collect();
+        let first = self.alphabet[0];
+
+        let mut result: Vec<String> = removals(&chars, self.len.start)
+            .into_iter()
+            .map(|x| x.into_iter().collect())
+            .collect();
+        for (i, c) in chars.iter().enumerate() {
+            if *c != first {
+                let mut candidate = chars.clone();
+                candidate[i] = first;
+                result.push(candidate.into_iter().collect());
+            }
+        }
+        result
+    }
+}
+
+macro_rules! impl_strategy_for_tuple {
+    ($($s:ident $i:tt),*) => {
+        /// Generates every component independently, and shrinks one component at a time.
+        impl<$($s: Strategy),*> Strategy for ($($s,)*) {
+            type Value = ($($s::Value,)*);
+
+            fn generate(&self, rng: &mut Rng) -> Self::Value {
+                ($(self.$i.generate(rng),)*)
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut result = vec![];
+                $(
+                    for smaller in self.$i.shrink(&value.$i) {
+                        let mut candidate = value.clone();
+                        candidate.$i = smaller;
+                        result.push(candidate);
+                    }
+                )*
+                result
+            }
+        }
+    };
+}
+
+impl_strategy_for_tuple!(A 0, B 1);
+impl_strategy_for_tuple!(A 0, B 1, C 2);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{counterexample, ints, strings, vecs, Config, Strategy};
+    use crate::rng::Rng;
+
+    #[test]
+    fn generates_values_in_range() {
+        let mut rng = Rng::new(3);
+        let strategy = (
+            ints(-5_i64..5),
+            vecs(ints(10_u8..12), 2..4),
+            strings("ab", 0..3),
+        );
+        for _ in 0..1000 {
+            let (int, vec, string) = strategy.generate(&mut rng);
+            assert!((-5..5).contains(&int));
+            assert!((2..4).contains(&vec.len()) && vec.iter().all(|x| (10..12).contains(x)));
+            assert!(string.len() < 3 && string.chars().all(|x| x == 'a' || x == 'b'));
+        }
+        let extremes = ints(u64::MIN..u64::MAX).generate(&mut rng);
+        assert!(extremes < u64::MAX);
+    }
+
+    #[test]
+    fn shrinks_integers_towards_zero() {
+        let config = Config::default();
+        let found = counterexample(&config, &ints(-1000_i32..1000), |x| *x < 137).unwrap();
+        assert_eq!(found.value, 137);
+        let found = counterexample(&config, &ints(-1000_i32..1000), |x| *x > -42).unwrap();
+        assert_eq!(found.value, -42);
+        // the target is the start of a range above zero.
+        let found = counterexample(&config, &ints(50_u64..100), |x| *x < 73).unwrap();
+        assert_eq!(found.value, 73);
+        assert!(counterexample(&config, &ints(0_u8..10), |x| *x < 10).is_none());
+    }
+
+    #[test]
+    fn shrinks_vectors_and_strings() {
+        let config = Config::default();
+        let sums_below = |values: &Vec<u32>| values.iter().sum::<u32>() < 100;
+        let found = counterexample(&config, &vecs(ints(0..80), 0..20), sums_below).unwrap();
+        assert_eq!(found.value.iter().sum::<u32>(), 100);
+        assert_eq!(found.value.len(), 2);
+
+        let no_double_hash = |x: &String| !x.contains("##");
+        let found = counterexample(&config, &strings(".#", 5..30), no_double_hash).unwrap();
+        assert_eq!(found.value.len(), 5);
+        assert_eq!(found.value.matches('#').count(), 2);
+    }
+
+    #[test]
+    fn catches_panics() {
+        let config = Config { cases: 50, seed: 9 };
+        let found = counterexample(&config, &(ints(0_u32..100), ints(0_u32..100)), |(a, b)| {
+            assert!(a + b < 50, "sum is too large");
+            true
+        })
+        .unwrap();
+        assert_eq!(found.value.0 + found.value.1, 50);
+        assert_eq!(found.panic.as_deref(), Some("sum is too large"));
+    }
+}
@@ -0,0 +1,411 @@
+use std::cmp::{max, min};
+use std::ops::Range;
+
+/// A set of values, stored as sorted, disjoint and non-adjacent half-open ranges.
+///
+/// Operations run in time proportional to the number of ranges, not their sizes.
+///
+/// ```
+/// # use advent_of_code::ranges::RangeSet;
+/// let a = RangeSet::from_iter([0..10, 20..30]);
+/// let b = RangeSet::from_iter([5..25]);
+/// assert_eq!(a.intersection(&b).ranges(), [5..10, 20..25]);
+/// assert_eq!(a.difference(&b).ranges(), [0..5, 25..30]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: Ord + Copy> RangeSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The disjoint ranges of the set in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|x| x.start)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|x| x.end <= value);
+        self.ranges.get(i).is_some_and(|x| x.contains(&value))
+    }
+
+    /// Adds a range to the set, merging it with overlapping or adjacent ranges.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // ranges in `start..end` overlap or touch the new range.
+        let start = self.ranges.partition_point(|x| x.end < range.start);
+        let end = self.ranges.partition_point(|x| x.start <= range.end);
+
+        let merged = if start < end {
+            min(range.start, self.ranges[start].start)..max(range.end, self.ranges[end - 1].end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(start..end, [merged]);
+    }
+
+    /// Returns the values that are in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        other.ranges.iter().for_each(|x| result.insert(x.clone()));
+        result
+    }
+
+    /// Returns the values that are in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = max(a.start, b.start)..min(a.end, b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the values that are in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                let cut = &other.ranges[k];
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = max(start, cut.end);
+                k += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|x| set.insert(x));
+        set
+    }
+}
+
+impl RangeSet<u64> {
+    /// The number of values in the set.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.ranges.iter().map(|x| x.end - x.start).sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise mapping of `u64` values that shifts source ranges onto destination ranges.
+/// Values outside of every source range map to themselves.
+///
+/// ```
+/// # use advent_of_code::ranges::RangeMap;
+/// let map = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+/// assert_eq!(map.get(99), 51);
+/// assert_eq!(map.get(10), 10);
+/// assert_eq!(map.map_range(45..55), [45..50, 52..57]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeMap {
+    /// Source ranges with their destination start, sorted by source and non-overlapping.
+    entries: Vec<(Range<u64>, u64)>,
+}
+
+impl RangeMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The source ranges with their destination start in ascending order.
+    #[must_use]
+    pub fn entries(&self) -> &[(Range<u64>, u64)] {
+        &self.entries
+    }
+
+    /// Maps the values in `src` onto `dst_start..dst_start + src.len()`.
+    ///
+    /// # Panics
+    /// Panics if `src` overlaps a range that is already mapped, or if the destination range ends after `u64::MAX`.
+    pub fn insert(&mut self, src: Range<u64>, dst_start: u64) {
+        if src.is_empty() {
+            return;
+        }
+        assert!(
+            dst_start.checked_add(src.end - src.start).is_some(),
+            "destination of source range {src:?} starting at {dst_start} does not fit into `u64`"
+        );
+
+        let i = self.entries.partition_point(|(x, _)| x.end <= src.start);
+        if let Some((next, _)) = self.entries.get(i) {
+            assert!(
+                next.start >= src.end,
+                "source range {src:?} overlaps {next:?}"
+            );
+        }
+
+        self.entries.insert(i, (src, dst_start));
+    }
+
+    /// Maps a single value.
+    #[must_use]
+    pub fn get(&self, value: u64) -> u64 {
+        let i = self.entries.partition_point(|(x, _)| x.end <= value);
+        match self.entries.get(i) {
+            Some((src, dst_start)) if src.contains(&value) => value - src.start + dst_start,
+            _ => value,
+        }
+    }
+
+    /// Splits `range` at the boundaries of the source ranges.
+    /// Returns every piece with the destination its start maps to, including unmapped pieces.
+    #[must_use]
+    pub fn split(&self, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let mut pieces = vec![];
+        let mut start = range.start;
+        let mut i = self.entries.partition_point(|(x, _)| x.end <= start);
+
+        while start < range.end {
+            match self.entries.get(i) {
+                Some((src, dst_start)) if src.start <= start => {
+                    let end = min(src.end, range.end);
+                    pieces.push((start..end, start - src.start + dst_start));
+                    start = end;
+                    i += 1;
+                }
+                Some((src, _)) => {
+                    let end = min(src.start, range.end);
+                    pieces.push((start..end, start));
+                    start = end;
+                }
+                None => {
+                    pieces.push((start..range.end, start));
+                    start = range.end;
+                }
+            }
+        }
+
+        pieces
+    }
+
+    /// Maps every value in `range`, returning the resulting ranges in the order of their sources.
+    #[must_use]
+    pub fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.split(range)
+            .into_iter()
+            .map(|(src, dst_start)| dst_start..dst_start + (src.end - src.start))
+            .collect()
+    }
+
+    /// Maps every value in `set`.
+    #[must_use]
+    pub fn map_set(&self, set: &RangeSet<u64>) -> RangeSet<u64> {
+        set.ranges()
+            .iter()
+            .flat_map(|x| self.map_range(x.clone()))
+            .collect()
+    }
+
+    /// Returns a map that applies this map first and `next` second.
+    ///
+    /// The result is defined on `0..u64::MAX`.
+    #[must_use]
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut entries: Vec<(Range<u64>, u64)> = vec![];
+
+        for (src, dst_start) in self.split(0..u64::MAX) {
+            // `insert` guarantees that every destination range fits.
+            let dst_end = dst_start + (src.end - src.start);
+            for (mid, next_dst) in next.split(dst_start..dst_end) {
+                let start = mid.start - dst_start + src.start;
+                let piece = start..start + (mid.end - mid.start);
+
+                // unmapped pieces are implicit, mapped pieces are merged with contiguous predecessors.
+                if piece.start == next_dst {
+                    continue;
+                }
+                match entries.last_mut() {
+                    Some((prev, prev_dst))
+                        if prev.end == piece.start
+                            && *prev_dst + (prev.end - prev.start) == next_dst =>
+                    {
+                        prev.end = piece.end;
+                    }
+                    _ => entries.push((piece, next_dst)),
+                }
+            }
+        }
+
+        RangeMap { entries }
+    }
+}
+
+impl FromIterator<(Range<u64>, u64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Range<u64>, u64)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        iter.into_iter()
+            .for_each(|(src, dst_start)| map.insert(src, dst_start));
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{RangeMap, RangeSet};
+    use crate::rng::Rng;
+    use std::collections::BTreeSet;
+    use std::ops::Range;
+
+    fn random_range(rng: &mut Rng) -> Range<u64> {
+        let start = rng.below(60);
+        start..start + rng.below(15)
+    }
+
+    fn values(set: &RangeSet<u64>) -> BTreeSet<u64> {
+        set.ranges().iter().cloned().flatten().collect()
+    }
+
+    fn random_map(rng: &mut Rng) -> RangeMap {
+        let mut map = RangeMap::new();
+        let mut start = rng.below(5);
+        for _ in 0..rng.below(5) {
+            let len = rng.below(10) + 1;
+            map.insert(start..start + len, rng.below(80));
+            start += len + rng.below(3);
+        }
+        map
+    }
+
+    #[test]
+    fn merges_inserted_ranges() {
+        let set = RangeSet::from_iter([10..20, 0..5, 5..7, 30..30, 15..25]);
+        assert_eq!(set.ranges(), [0..7, 10..25]);
+        assert!(set.contains(6));
+        assert!(!set.contains(7));
+        assert_eq!(set.min(), Some(0));
+        assert_eq!(set.size(), 22);
+    }
+
+    #[test]
+    fn set_operations_match_brute_force() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        for _ in 0..500 {
+            let a: RangeSet<u64> = (0..rng.below(5)).map(|_| random_range(&mut rng)).collect();
+            let b: RangeSet<u64> = (0..rng.below(5)).map(|_| random_range(&mut rng)).collect();
+            let (va, vb) = (values(&a), values(&b));
+
+            assert_eq!(values(&a.union(&b)), &va | &vb);
+            assert_eq!(values(&a.intersection(&b)), &va & &vb);
+            assert_eq!(values(&a.difference(&b)), &va - &vb);
+        }
+    }
+
+    #[test]
+    fn maps_values_and_ranges() {
+        let map = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(100), 100);
+        assert_eq!(map.map_range(40..100), [40..50, 52..100, 50..52]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_overlapping_sources() {
+        RangeMap::from_iter([(0..10, 50), (5..15, 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into `u64`")]
+    fn rejects_destinations_past_the_end() {
+        RangeMap::from_iter([(0..10, u64::MAX - 5)]);
+    }
+
+    #[test]
+    fn composes_maps_at_the_end_of_the_domain() {
+        let map = RangeMap::from_iter([(0..10, u64::MAX - 10)]);
+        let next = RangeMap::from_iter([(u64::MAX - 4..u64::MAX, 0)]);
+        let composed = map.then(&next);
+        assert_eq!(composed.get(0), u64::MAX - 10);
+        assert_eq!(composed.get(9), 3);
+        assert_eq!(
+            composed.map_range(0..10),
+            [u64::MAX - 10..u64::MAX - 4, 0..4]
+        );
+    }
+
+    #[test]
+    fn range_mapping_matches_brute_force() {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let map = random_map(&mut rng);
+            let next = random_map(&mut rng);
+            let composed = map.then(&next);
+            let set: RangeSet<u64> = (0..3).map(|_| random_range(&mut rng)).collect();
+
+            let expected: BTreeSet<u64> = values(&set).iter().map(|x| map.get(*x)).collect();
+            assert_eq!(values(&map.map_set(&set)), expected);
+
+            for value in 0..120 {
+                assert_eq!(composed.get(value), next.get(map.get(value)));
+            }
+        }
+    }
+}
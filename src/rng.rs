@@ -0,0 +1,124 @@
+use std::ops::Range;
+
+/// A small seeded pseudo-random number generator (SplitMix64).
+///
+/// Generated inputs and randomized tests are reproducible from their seed, without extra dependencies.
+/// Not suitable for anything that needs unpredictable numbers.
+///
+/// ```
+/// # use advent_of_code::rng::Rng;
+/// let mut rng = Rng::new(42);
+/// let roll = rng.range(1..7);
+/// assert!((1..7).contains(&roll));
+/// assert_eq!(Rng::new(42).range(1..7), roll);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`.
+    ///
+    /// # Panics
+    /// Panics if `bound` is `0`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must not be zero");
+        // NOTE: the high half of the product is smaller than `bound`, the slight bias does not matter here.
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Returns a number in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "range must not be empty");
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// Returns a signed number in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range_i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "range must not be empty");
+        // NOTE: the width of any `i64` range fits into `u64`.
+        let width = range.end.wrapping_sub(range.start) as u64;
+        range.start.wrapping_add(self.below(width) as i64)
+    }
+
+    /// Returns an index into a collection of length `len`.
+    ///
+    /// # Panics
+    /// Panics if `len` is `0`.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// Returns a random element of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn stays_in_bounds() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((10..12).contains(&rng.range(10..12)));
+            assert!((-5..5).contains(&rng.range_i64(-5..5)));
+        }
+        assert!((i64::MIN..i64::MAX).contains(&rng.range_i64(i64::MIN..i64::MAX)));
+    }
+
+    #[test]
+    fn covers_range_evenly() {
+        let mut rng = Rng::new(0);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.index(6)] += 1;
+        }
+        assert!(counts.iter().all(|x| (900..1100).contains(x)), "{counts:?}");
+
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,323 @@
+//! Snapshot tests: compare the `Debug` output of intermediate values to files in `data/snapshots`.
+//!
+//! A missing snapshot is written on the first run. If a snapshot differs, the test fails with a unified diff,
+//! unless the `AOC_BLESS` environment variable is set, which overwrites the snapshot instead.
+//! When the `CI` environment variable is set, missing snapshots fail too, so they cannot be forgotten in a commit.
+
+use std::env;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Day;
+
+/// Set this environment variable to overwrite snapshots that differ, e.g. `AOC_BLESS=1 cargo test`.
+pub const BLESS_VAR: &str = "AOC_BLESS";
+
+/// Set by CI services. Missing snapshots fail instead of being created, unless [`BLESS_VAR`] is set.
+pub const CI_VAR: &str = "CI";
+
+/// The lines of context around every change in a diff.
+const CONTEXT: usize = 3;
+
+/// What happened to a snapshot file.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnapshotStatus {
+    Matched,
+    Created,
+    Blessed,
+}
+
+/// Which snapshots [`check_snapshot`] writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Create missing snapshots, the default for local runs.
+    Missing,
+    /// Create missing snapshots and overwrite snapshots that differ, when [`BLESS_VAR`] is set.
+    All,
+    /// Write nothing, when [`CI_VAR`] is set.
+    Nothing,
+}
+
+/// Why a snapshot check failed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot file does not exist.
+    Missing,
+    /// The snapshot differs, with a unified diff from the snapshot to the actual value.
+    Differs(String),
+}
+
+impl Error for SnapshotError {}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Missing => {
+                write!(
+                    f,
+                    "does not exist, run the test locally or with {BLESS_VAR}=1 to create it"
+                )
+            }
+            SnapshotError::Differs(diff) => {
+                write!(
+                    f,
+                    "does not match, run with {BLESS_VAR}=1 to update it:\n{diff}"
+                )
+            }
+        }
+    }
+}
+
+/// Compares the pretty `Debug` output of `value` to `data/snapshots/<day>-<name>.snap`.
+///
+/// # Panics
+/// Panics with a unified diff if the snapshot differs and [`BLESS_VAR`] is not set,
+/// if the snapshot is missing and only [`CI_VAR`] is set, or if `name` is not made of lowercase letters, digits, `-` and `_`.
+pub fn assert_snapshot<T: Debug + ?Sized>(day: Day, name: &str, value: &T) {
+    assert!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'),
+        "invalid snapshot name `{name}`"
+    );
+
+    let path = env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("snapshots")
+        .join(format!("{day}-{name}.snap"));
+    let is_set = |var: &str| env::var_os(var).is_some_and(|x| !x.is_empty());
+    let update = if is_set(BLESS_VAR) {
+        Update::All
+    } else if is_set(CI_VAR) {
+        Update::Nothing
+    } else {
+        Update::Missing
+    };
+
+    match check_snapshot(&path, &format!("{value:#?}\n"), update) {
+        Ok(SnapshotStatus::Matched) => {}
+        Ok(status) => eprintln!("{status:?} snapshot \"{}\".", path.display()),
+        Err(err) => panic!("snapshot \"{}\" {err}", path.display()),
+    }
+}
+
+/// Compares `actual` to the snapshot at `path`, and writes the snapshot if `update` allows it.
+///
+/// # Errors
+/// Returns an error if the snapshot is missing or differs and `update` does not allow writing it.
+///
+/// # Panics
+/// Panics if the snapshot exists but cannot be read, or if it cannot be written.
+pub fn check_snapshot(
+    path: &Path,
+    actual: &str,
+    update: Update,
+) -> Result<SnapshotStatus, SnapshotError> {
+    let write = || {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("could not write snapshot \"{}\": {e}", path.display()));
+    };
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if update == Update::Nothing {
+                return Err(SnapshotError::Missing);
+            }
+            write();
+            return Ok(SnapshotStatus::Created);
+        }
+        Err(e) => panic!("could not read snapshot \"{}\": {e}", path.display()),
+    };
+
+    // snapshots may be checked out with Windows line endings.
+    let expected = expected.replace("\r\n", "\n");
+    if expected == actual {
+        Ok(SnapshotStatus::Matched)
+    } else if update == Update::All {
+        write();
+        Ok(SnapshotStatus::Blessed)
+    } else {
+        Err(SnapshotError::Differs(unified_diff(&expected, actual)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Returns a unified diff from `old` to `new` with three lines of context, or an empty string if the lines are equal.
+#[must_use]
+pub fn unified_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut lcs = vec![vec![0_u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // every edit with the lines of `old` and `new` it is at.
+    let mut edits = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        let edit = if i < old.len() && j < new.len() && old[i] == new[j] {
+            Edit::Keep
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            Edit::Remove
+        } else {
+            Edit::Add
+        };
+        edits.push((edit, i, j));
+        match edit {
+            Edit::Keep => (i, j) = (i + 1, j + 1),
+            Edit::Remove => i += 1,
+            Edit::Add => j += 1,
+        }
+    }
+
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|x| edits[*x].0 != Edit::Keep)
+        .collect();
+    let mut diff = String::new();
+    let mut rest = &changes[..];
+
+    while let Some(&first) = rest.first() {
+        // changes that are close to each other share a hunk.
+        let len = rest
+            .windows(2)
+            .position(|w| w[1] - w[0] > 2 * CONTEXT)
+            .map_or(rest.len(), |x| x + 1);
+        let last = rest[len - 1];
+        rest = &rest[len..];
+
+        let hunk = &edits[first.saturating_sub(CONTEXT)..(last + CONTEXT + 1).min(edits.len())];
+        let old_len = hunk.iter().filter(|x| x.0 != Edit::Add).count();
+        let new_len = hunk.iter().filter(|x| x.0 != Edit::Remove).count();
+        // empty ranges start at the line before, like in `diff -u`.
+        let start = |index: usize, len: usize| if len == 0 { index } else { index + 1 };
+        diff.push_str(&format!(
+            "@@ -{},{old_len} +{},{new_len} @@\n",
+            start(hunk[0].1, old_len),
+            start(hunk[0].2, new_len)
+        ));
+
+        for &(edit, i, j) in hunk {
+            let line = match edit {
+                Edit::Keep => format!(" {}", old[i]),
+                Edit::Remove => format!("-{}", old[i]),
+                Edit::Add => format!("+{}", new[j]),
+            };
+            diff.push_str(&line);
+            diff.push('\n');
+        }
+    }
+
+    diff
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_snapshot, unified_diff, SnapshotError, SnapshotStatus, Update};
+    use std::{env, fs, process};
+
+    #[test]
+    fn diffs_lines() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n"), "");
+        assert_eq!(
+            unified_diff("a\nb\nc\n", "a\nx\nc\nd\n"),
+            "@@ -1,3 +1,4 @@\n a\n-b\n+x\n c\n+d\n"
+        );
+        assert_eq!(unified_diff("", "a\n"), "@@ -0,0 +1,1 @@\n+a\n");
+    }
+
+    #[test]
+    fn splits_distant_changes_into_hunks() {
+        let old: String = (1..=20).map(|x| format!("{x}\n")).collect();
+        let new: String = (1..=20)
+            .filter(|x| *x != 18)
+            .map(|x| {
+                if x == 2 {
+                    "two\n".to_string()
+                } else {
+                    format!("{x}\n")
+                }
+            })
+            .collect();
+        assert_eq!(
+            unified_diff(&old, &new),
+            "@@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n@@ -15,6 +15,5 @@\n 15\n 16\n 17\n-18\n 19\n 20\n"
+        );
+    }
+
+    #[test]
+    fn creates_checks_and_blesses_snapshots() {
+        let dir = env::temp_dir().join(format!("aoc-snapshots-{}", process::id()));
+        let path = dir.join("01-test.snap");
+
+        assert_eq!(
+            check_snapshot(&path, "a\n", Update::Nothing),
+            Err(SnapshotError::Missing)
+        );
+        assert!(!path.exists());
+        assert_eq!(
+            check_snapshot(&path, "a\n", Update::Missing),
+            Ok(SnapshotStatus::Created)
+        );
+        assert_eq!(
+            check_snapshot(&path, "a\n", Update::Nothing),
+            Ok(SnapshotStatus::Matched)
+        );
+        assert_eq!(
+            check_snapshot(&path, "b\n", Update::Missing),
+            Err(SnapshotError::Differs(
+                "@@ -1,1 +1,1 @@\n-a\n+b\n".to_string()
+            ))
+        );
+        assert_eq!(
+            check_snapshot(&path, "b\n", Update::All),
+            Ok(SnapshotStatus::Blessed)
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b\n");
+
+        fs::write(&path, "b\r\n").unwrap();
+        assert_eq!(
+            check_snapshot(&path, "b\n", Update::Missing),
+            Ok(SnapshotStatus::Matched)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_unreadable_snapshots() {
+        let dir = env::temp_dir().join(format!("aoc-snapshots-invalid-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01-test.snap");
+        fs::write(&path, [0xff, 0xfe]).unwrap();
+
+        let result = std::panic::catch_unwind(|| check_snapshot(&path, "a\n", Update::All));
+        assert_eq!(fs::read(&path).unwrap(), [0xff, 0xfe]);
+        fs::remove_dir_all(dir).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,8 @@
 use std::io;
 
 use crate::template::{
+    output::{bold, italic},
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
@@ -14,7 +14,7 @@ pub fn handle(is_release: bool, is_timed: bool) {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("{}", bold(format!("Day {day}")));
         println!("------");
 
         let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
@@ -30,7 +30,11 @@ pub fn handle(is_release: bool, is_timed: bool) {
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        println!(
+            "\n{} {}",
+            bold("Total:"),
+            italic(format!("{total_millis:.2}ms"))
+        );
 
         if is_release {
             match readme_benchmarks::update(timings, total_millis) {
@@ -65,6 +69,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::output;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -81,15 +86,19 @@ mod child_commands {
         }
 
         let day_padded = day.to_string();
+        let color_arg = output::child_color_arg();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        // stdout is piped below, so mirror the resolved color choice to child invocations.
+        args.push("--");
+        args.push(&color_arg);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
@@ -171,7 +180,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -0,0 +1,25 @@
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::generators;
+use crate::Day;
+
+pub const DEFAULT_SIZE: usize = 100;
+
+pub fn handle(day: Day, seed: Option<u64>, size: usize) {
+    let Some(generator) = generators::for_day(day) else {
+        eprintln!("No input generator for day {day}.");
+        process::exit(1);
+    };
+
+    // NOTE: truncating the nanoseconds still gives a new seed for every run.
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_nanos() as u64)
+    });
+
+    // the seed goes to stderr, so stdout can be redirected into an input file.
+    eprintln!("Generating input for day {day} with --seed {seed} --size {size}.");
+    print!("{}", generator.generate(seed, size));
+}
@@ -8,11 +8,11 @@ use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Option<u32> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
@@ -34,7 +34,49 @@ mod tests {
         let result = part_two(INPUT);
         assert_eq!(result, None);
     }
-}
+PROPERTY_TESTS}
+"#;
+
+/// Property tests that are added to [`MODULE_TEMPLATE`] with `--properties`.
+const PROPERTY_TEMPLATE: &str = r#"
+    mod properties {
+        use super::*;
+        use advent_of_code::property::{check, ints, vecs};
+        use advent_of_code::Answer;
+
+        /// Renders generated lines of numbers as an input. Adapt this and the strategies to the puzzle's input format.
+        fn render(lines: &[Vec<u32>]) -> String {
+            lines
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        + "\n"
+                })
+                .collect()
+        }
+
+        /// The value of an integer answer.
+        fn int(answer: Option<impl Into<Answer>>) -> Option<i128> {
+            match answer.map(Into::into) {
+                Some(Answer::Int(x)) => Some(x),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn test_part_two_at_least_part_one() {
+            check(vecs(vecs(ints(0_u32..100), 1..10), 1..20), |lines| {
+                let input = render(lines);
+                match (int(part_one(&input)), int(part_two(&input))) {
+                    (Some(one), Some(two)) => two >= one,
+                    _ => true,
+                }
+            });
+        }
+    }
 "#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -42,10 +84,14 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, properties: bool) {
     let input_path = format!("data/inputs/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
@@ -60,6 +106,10 @@ pub fn handle(day: Day) {
     match file.write_all(
         MODULE_TEMPLATE
             .replace("DAY_NUMBER", &day.into_inner().to_string())
+            .replace(
+                "PROPERTY_TESTS",
+                if properties { PROPERTY_TEMPLATE } else { "" },
+            )
             .as_bytes(),
     ) {
         Ok(()) => {
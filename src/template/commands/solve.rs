@@ -1,8 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+use crate::template::output;
+use crate::template::trace::TraceTarget;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    input: Option<&str>,
+    inputs: Option<&str>,
+    trace: Option<TraceTarget>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
@@ -10,6 +20,7 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.push(output::child_color_arg());
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
@@ -20,6 +31,21 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(input) = input {
+        // stdin is inherited below, so `-` is read by the solution itself.
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if let Some(trace) = trace {
+        cmd_args.push(trace.flag().to_string());
+    }
+
+    if let Some(inputs) = inputs {
+        cmd_args.push("--inputs".to_string());
+        cmd_args.push(inputs.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +53,10 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // forward failures, e.g. mismatched answers when running `--inputs`.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
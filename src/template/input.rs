@@ -0,0 +1,201 @@
+//! Resolves where a solution reads its puzzle input from.
+
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs, process};
+
+use crate::template::arg_value;
+use crate::Day;
+
+/// A source of puzzle input, as passed via `--input`.
+///
+/// `-` reads from stdin, anything else is treated as a path relative to the current directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// The personal puzzle input for a day, i.e. `data/inputs/<day>.txt` in the current directory.
+    #[must_use]
+    pub fn for_day(day: Day) -> Self {
+        let cwd = env::current_dir().unwrap();
+        InputSource::File(cwd.join("data").join("inputs").join(format!("{day}.txt")))
+    }
+
+    /// Reads the whole input into a string.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => f.write_str("stdin"),
+            InputSource::File(path) => write!(f, "\"{}\"", path.display()),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(path.into())),
+        }
+    }
+}
+
+/// Reads the input passed via `--input`, falling back to the personal input for `day`.
+/// Exits with an error message naming the attempted source if it can not be read.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let source = match arg_value(&args, "--input") {
+        Some(value) => value.parse().unwrap_or_else(|e| match e {}),
+        None => InputSource::for_day(day),
+    };
+
+    match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: could not read input from {source}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// An input read from a directory passed via `--inputs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInput {
+    /// The file name of the input.
+    pub name: String,
+    pub input: String,
+    /// Expected answers for part 1 and 2, read from a sidecar file next to the input.
+    pub expected: [Option<String>; 2],
+}
+
+impl NamedInput {
+    #[must_use]
+    pub fn has_expected(&self) -> bool {
+        self.expected.iter().any(Option::is_some)
+    }
+}
+
+/// Extension of sidecar files with expected answers, e.g. `alice.expected` for `alice.txt`.
+///
+/// The first line holds the answer for part 1, the second line the answer for part 2.
+/// Empty lines or a `-` skip the check for that part.
+pub const EXPECTED_EXTENSION: &str = "expected";
+
+/// Reads every input file in `dir` in file name order, together with its expected answers.
+pub fn read_inputs(dir: &Path) -> io::Result<Vec<NamedInput>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|x| x.path()))
+        .collect::<io::Result<_>>()?;
+
+    paths.retain(|path| path.is_file() && path.extension().is_none_or(|x| x != EXPECTED_EXTENSION));
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let expected_path = path.with_extension(EXPECTED_EXTENSION);
+            let expected = if expected_path.exists() {
+                parse_expected(&fs::read_to_string(expected_path)?)
+            } else {
+                [None, None]
+            };
+
+            Ok(NamedInput {
+                name: path.file_name().unwrap().to_string_lossy().into_owned(),
+                input: fs::read_to_string(&path)?,
+                expected,
+            })
+        })
+        .collect()
+}
+
+fn parse_expected(s: &str) -> [Option<String>; 2] {
+    let mut lines = s.lines().map(|line| match line.trim() {
+        "" | "-" => None,
+        answer => Some(answer.to_string()),
+    });
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+/// Reads the inputs in the directory passed via `--inputs`, if any.
+/// Exits with an error message naming the directory if it can not be read.
+#[must_use]
+pub fn read_inputs_from_args() -> Option<Vec<NamedInput>> {
+    let args: Vec<String> = env::args().collect();
+    let dir = Path::new(arg_value(&args, "--inputs")?);
+
+    match read_inputs(dir) {
+        Ok(inputs) => Some(inputs),
+        Err(e) => {
+            eprintln!(
+                "Error: could not read inputs from \"{}\": {e}",
+                dir.display()
+            );
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_expected, InputSource};
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_input_source() {
+        assert_eq!("-".parse(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "foo/bar.txt".parse(),
+            Ok(InputSource::File(PathBuf::from("foo/bar.txt")))
+        );
+    }
+
+    #[test]
+    fn defaults_to_personal_input() {
+        let InputSource::File(path) = InputSource::for_day(day!(5)) else {
+            panic!("expected a file source");
+        };
+        assert!(path.ends_with("data/inputs/05.txt"));
+    }
+
+    #[test]
+    fn displays_attempted_path() {
+        assert_eq!(
+            InputSource::File("foo/bar.txt".into()).to_string(),
+            "\"foo/bar.txt\""
+        );
+        assert_eq!(InputSource::Stdin.to_string(), "stdin");
+    }
+
+    #[test]
+    fn parses_expected_answers() {
+        assert_eq!(
+            parse_expected("6440\n5905\n"),
+            [Some("6440".into()), Some("5905".into())]
+        );
+        assert_eq!(parse_expected("-\n5905"), [None, Some("5905".into())]);
+        assert_eq!(parse_expected("6440"), [Some("6440".into()), None]);
+    }
+}
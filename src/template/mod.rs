@@ -3,20 +3,19 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod input;
+pub mod output;
 pub mod readme_benchmarks;
 pub mod runner;
-
-pub const ANSI_ITALIC: &str = "\x1b[3m";
-pub const ANSI_BOLD: &str = "\x1b[1m";
-pub const ANSI_RESET: &str = "\x1b[0m";
+pub mod trace;
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -27,8 +26,24 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
+/// Returns the value of a `--name value` or `--name=value` argument.
+pub(crate) fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == name {
+            return iter.next().map(String::as_str);
+        }
+        if let Some(value) = arg.strip_prefix(name).and_then(|x| x.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+
+    None
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
@@ -40,7 +55,11 @@ macro_rules! solution {
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
+            if let Some(inputs) = advent_of_code::template::input::read_inputs_from_args() {
+                run_inputs(part_one, part_two, &inputs);
+                return;
+            }
+            let input = advent_of_code::template::input::read_input(DAY);
             run_part(part_one, &input, DAY, 1);
             run_part(part_two, &input, DAY, 2);
         }
@@ -0,0 +1,188 @@
+//! Terminal styling for everything the template prints to stdout.
+//! Escape codes are only emitted when colors are enabled, and carriage-return rewrites only when stdout is a terminal.
+
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{stdout, IsTerminal};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::template::arg_value;
+
+const ANSI_ITALIC: &str = "\x1b[3m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+static COLORS_ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Whether styled output should be used, as passed via `--color` or `--no-color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Use colors if stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolves the choice against the environment.
+    #[must_use]
+    pub fn is_enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty());
+                !no_color && is_interactive()
+            }
+        }
+    }
+}
+
+impl Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        })
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = ColorChoiceFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(ColorChoiceFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ColorChoice`].
+#[derive(Debug)]
+pub struct ColorChoiceFromStrError;
+
+impl Error for ColorChoiceFromStrError {}
+
+impl Display for ColorChoiceFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `auto`, `always` or `never`")
+    }
+}
+
+/// Sets the color choice for this process. Has no effect if output was already styled.
+pub fn init(choice: ColorChoice) {
+    let _ = COLORS_ENABLED.set(choice.is_enabled());
+}
+
+/// Returns `true` if escape codes should be written to stdout.
+/// Solution binaries are not initialized explicitly and pick up `--color` / `--no-color` from their arguments.
+pub fn colors_enabled() -> bool {
+    *COLORS_ENABLED.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        color_choice_from_args(&args).is_enabled()
+    })
+}
+
+/// Returns `true` if stdout is a terminal, i.e. lines can be rewritten in place.
+pub fn is_interactive() -> bool {
+    static INTERACTIVE: OnceLock<bool> = OnceLock::new();
+    *INTERACTIVE.get_or_init(|| stdout().is_terminal())
+}
+
+/// The resolved choice to mirror to child invocations, so piping their stdout does not change styling.
+#[must_use]
+pub fn child_color_arg() -> String {
+    let choice = if colors_enabled() {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    };
+    format!("--color={choice}")
+}
+
+fn color_choice_from_args(args: &[String]) -> ColorChoice {
+    if args.iter().any(|x| x == "--no-color") {
+        return ColorChoice::Never;
+    }
+
+    arg_value(args, "--color")
+        .and_then(|x| x.parse().ok())
+        .unwrap_or_default()
+}
+
+/// A value that is wrapped in an escape sequence when colors are enabled.
+pub struct Styled<T> {
+    style: &'static str,
+    value: T,
+}
+
+impl<T: Display> Display for Styled<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if colors_enabled() {
+            write!(f, "{}{}{ANSI_RESET}", self.style, self.value)
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+pub fn bold<T: Display>(value: T) -> Styled<T> {
+    Styled {
+        style: ANSI_BOLD,
+        value,
+    }
+}
+
+pub fn italic<T: Display>(value: T) -> Styled<T> {
+    Styled {
+        style: ANSI_ITALIC,
+        value,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{color_choice_from_args, ColorChoice};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_color_choice() {
+        assert_eq!("auto".parse::<ColorChoice>().unwrap(), ColorChoice::Auto);
+        assert_eq!(
+            "always".parse::<ColorChoice>().unwrap(),
+            ColorChoice::Always
+        );
+        assert_eq!("never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn reads_color_choice_from_args() {
+        assert_eq!(
+            color_choice_from_args(&args("01 --time")),
+            ColorChoice::Auto
+        );
+        assert_eq!(
+            color_choice_from_args(&args("01 --no-color")),
+            ColorChoice::Never
+        );
+        assert_eq!(
+            color_choice_from_args(&args("01 --color=always --time")),
+            ColorChoice::Always
+        );
+        assert_eq!(
+            color_choice_from_args(&args("01 --color never")),
+            ColorChoice::Never
+        );
+    }
+}
@@ -1,19 +1,33 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
+use crate::template::{
+    aoc_cli,
+    input::NamedInput,
+    output::{self, bold, italic},
+    trace,
+};
+use crate::{Answer, Day};
 use std::io::{stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use super::ANSI_BOLD;
-
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
+    let func = |input| func(input).map(Into::into);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, part, |result| {
+        print_result(result, &part_str, "");
+
+        if is_timed() && output::is_interactive() {
+            print!(" > {}", italic("benching"));
+            let _ = stdout().flush();
+        }
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
@@ -22,21 +36,124 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
     }
 }
 
+/// Run both parts against every input and print a table of answers and timings.
+/// Answers are checked against the expected answers of an input if present, exits with a non-zero status on mismatch.
+pub fn run_inputs<T1: Into<Answer>, T2: Into<Answer>>(
+    part_one: impl Fn(&str) -> Option<T1>,
+    part_two: impl Fn(&str) -> Option<T2>,
+    inputs: &[NamedInput],
+) {
+    let mut rows = vec![vec![
+        "Input".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+    let mut mismatches = 0;
+
+    for input in inputs {
+        let [expected_1, expected_2] = &input.expected;
+        let (part_1, ok_1) = run_cell(&part_one, &input.input, 1, expected_1.as_deref());
+        let (part_2, ok_2) = run_cell(&part_two, &input.input, 2, expected_2.as_deref());
+
+        if !(ok_1 && ok_2) {
+            mismatches += 1;
+        }
+
+        rows.push(vec![input.name.clone(), part_1, part_2]);
+    }
+
+    print_table(&rows);
+
+    let checked = inputs.iter().filter(|x| x.has_expected()).count();
+    if checked > 0 {
+        println!();
+        println!(
+            "{} of {checked} checked inputs match their expected answers.",
+            checked - mismatches
+        );
+    }
+
+    if mismatches > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_cell<T: Into<Answer>>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    part: u8,
+    expected: Option<&str>,
+) -> (String, bool) {
+    let func = |input| func(input).map(Into::<Answer>::into);
+    let (result, duration, samples) = run_timed(func, input, part, |_| {});
+    let duration_str = format_duration(&duration, samples);
+
+    let Some(result) = result else {
+        let ok = expected.is_none();
+        return (format!("✖{duration_str}"), ok);
+    };
+
+    let answer = if result.is_multiline() {
+        "▼".to_string()
+    } else {
+        result.to_string()
+    };
+
+    match expected {
+        Some(expected) if result.matches(expected) => (format!("{answer} ✔{duration_str}"), true),
+        Some(expected) => (
+            format!("{answer} ✖ expected {expected}{duration_str}"),
+            false,
+        ),
+        None => (format!("{answer}{duration_str}"), true),
+    }
+}
+
+fn print_table(rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for (i, row) in rows.iter().enumerate() {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        if i == 0 {
+            println!("{}", bold(line.trim_end()));
+        } else {
+            println!("{}", line.trim_end());
+        }
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: u8,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
+    // only trace the first execution, not every bench iteration.
+    trace::set_part(part);
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
+    trace::set_part(0);
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed() {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -45,19 +162,13 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -92,26 +203,34 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
+    // intermediate results are overwritten with a carriage return, which only works on a terminal.
+    let is_interactive = output::is_interactive();
+
+    if is_intermediate_result && !is_interactive {
+        return;
+    }
+
+    if !is_intermediate_result && is_interactive {
+        print!("\r");
+    }
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            if result.is_multiline() {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {}{duration_str}", bold(result));
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
                     println!("{str}");
                 }
             }
@@ -119,9 +238,11 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
         None => {
             if is_intermediate_result {
                 print!("{part}: ✖");
-            } else {
-                print!("\r");
+            } else if is_interactive {
+                // pad to clear the remains of the intermediate line.
                 println!("{part}: ✖             ");
+            } else {
+                println!("{part}: ✖");
             }
         }
     }
@@ -130,8 +251,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result(
+    result: Answer,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -162,6 +283,11 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    if result.is_multiline() {
+        eprintln!("Multi-line answers can not be submitted directly. Read the answer from the output and submit it via aoc-cli.");
+        process::exit(1);
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &result.normalized()))
 }
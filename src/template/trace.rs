@@ -0,0 +1,160 @@
+//! Debug output for solutions that does not interfere with the `Part N:` lines printed to stdout.
+//!
+//! Traces are only written when `solve` is called with `--verbose` (to stderr) or `--log` (to `data/logs/<day>.log`),
+//! and only during the first, timed execution of a part. In release builds, [`trace!`](crate::trace) compiles to nothing.
+
+use std::fmt::{self, Display};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::{env, process};
+
+use crate::Day;
+
+/// Where traces are written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceTarget {
+    Stderr,
+    LogFile,
+}
+
+impl TraceTarget {
+    /// The flag that selects this target.
+    #[must_use]
+    pub fn flag(self) -> &'static str {
+        match self {
+            TraceTarget::Stderr => "--verbose",
+            TraceTarget::LogFile => "--log",
+        }
+    }
+
+    fn from_args(args: &[String]) -> Option<Self> {
+        if args.iter().any(|x| x == "--log") {
+            Some(TraceTarget::LogFile)
+        } else if args.iter().any(|x| x == "--verbose") {
+            Some(TraceTarget::Stderr)
+        } else {
+            None
+        }
+    }
+}
+
+enum Sink {
+    Stderr,
+    File(File),
+}
+
+static SINK: OnceLock<Option<Mutex<Sink>>> = OnceLock::new();
+
+/// The part that is currently running, `0` if traces should be discarded.
+static PART: AtomicU8 = AtomicU8::new(0);
+
+/// Tags subsequent traces with `part`. Pass `0` to discard traces, e.g. while benching.
+pub(crate) fn set_part(part: u8) {
+    PART.store(part, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn get_log_path(day: Day) -> PathBuf {
+    PathBuf::from("data")
+        .join("logs")
+        .join(format!("{day}.log"))
+}
+
+fn open_sink(day: Day) -> Option<Mutex<Sink>> {
+    let args: Vec<String> = env::args().collect();
+
+    let sink = match TraceTarget::from_args(&args)? {
+        TraceTarget::Stderr => Sink::Stderr,
+        TraceTarget::LogFile => {
+            let path = get_log_path(day);
+            let file = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| File::create(&path));
+
+            match file {
+                Ok(file) => Sink::File(file),
+                Err(e) => {
+                    eprintln!(
+                        "Error: could not create log file \"{}\": {e}",
+                        path.display()
+                    );
+                    process::exit(1);
+                }
+            }
+        }
+    };
+
+    Some(Mutex::new(sink))
+}
+
+/// Formats a trace line, e.g. `[day 05 part 2] message`.
+fn format_line(day: Day, part: u8, args: impl Display) -> String {
+    format!("[day {day} part {part}] {args}\n")
+}
+
+// Not part of the public API, use `trace!` instead.
+#[doc(hidden)]
+pub fn __write(day: Day, args: fmt::Arguments) {
+    let part = PART.load(Ordering::Relaxed);
+    if part == 0 {
+        return;
+    }
+
+    let Some(sink) = SINK.get_or_init(|| open_sink(day)) else {
+        return;
+    };
+
+    let line = format_line(day, part, args);
+    let mut sink = sink.lock().unwrap();
+    let _ = match &mut *sink {
+        Sink::Stderr => io::stderr().write_all(line.as_bytes()),
+        Sink::File(file) => file.write_all(line.as_bytes()),
+    };
+}
+
+/// Writes a debug message tagged with the current day and part, using the same syntax as [`println!`].
+///
+/// Messages are discarded unless `solve` is called with `--verbose` or `--log`, and compiled out of release builds.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::template::trace::__write(DAY, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_line, TraceTarget};
+    use crate::day;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn formats_tagged_line() {
+        assert_eq!(
+            format_line(day!(5), 2, format_args!("seeds: {}", 4)),
+            "[day 05 part 2] seeds: 4\n"
+        );
+    }
+
+    #[test]
+    fn reads_target_from_args() {
+        assert_eq!(TraceTarget::from_args(&args("05 --time")), None);
+        assert_eq!(
+            TraceTarget::from_args(&args("05 --verbose")),
+            Some(TraceTarget::Stderr)
+        );
+        assert_eq!(
+            TraceTarget::from_args(&args("05 --verbose --log")),
+            Some(TraceTarget::LogFile)
+        );
+    }
+}